@@ -0,0 +1,23 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use numpy::pyo3::{types::PyAnyMethods, Python};
+use pyo3_arraylike::PyArrayLike2;
+
+fn extract(c: &mut Criterion) {
+    Python::with_gil(|py| {
+        let numpy = py.import_bound("numpy").unwrap();
+        let array = numpy.call_method1("ones", ((100, 100),)).unwrap();
+        let list = array.call_method0("tolist").unwrap();
+
+        let mut group = c.benchmark_group("extract 100x100");
+        group.bench_function("numpy array", |b| {
+            b.iter(|| array.extract::<PyArrayLike2<f64>>().unwrap())
+        });
+        group.bench_function("nested list", |b| {
+            b.iter(|| list.extract::<PyArrayLike2<f64>>().unwrap())
+        });
+        group.finish();
+    });
+}
+
+criterion_group!(benches, extract);
+criterion_main!(benches);
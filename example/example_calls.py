@@ -1,5 +1,5 @@
 import numpy as np
-from example import sum_of_rows
+from example import normalize_rows, sum_of_rows, sum_of_rows_view
 
 def call1():
     """Succeeds by passing a reference of the input array to the rust component."""
@@ -15,4 +15,12 @@ def call3():
 
 def call4():
     """Raises an error since the input array contains a value which cannot be safely casted to u32."""
-    return sum_of_rows([[2**32,0,0], [0,0,0]])
\ No newline at end of file
+    return sum_of_rows([[2**32,0,0], [0,0,0]])
+
+def call5():
+    """Succeeds by returning a new numpy array built inside the rust component."""
+    return normalize_rows([[3,4], [0,2]])
+
+def call6():
+    """Succeeds like call2, with the parameter declared as an array view via the `arraylike` attribute."""
+    return sum_of_rows_view([[1,2,3], [4,5,6]])
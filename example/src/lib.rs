@@ -1,15 +1,30 @@
-use ndarray::Axis;
+use ndarray::{ArrayView2, Axis};
 use numpy::{IntoPyArray, PyArray1};
 use pyo3::{pyfunction, pymodule, types::PyModule, wrap_pyfunction, Bound, PyResult, Python};
-use pyo3_arraylike::PyArrayLike2;
+use pyo3_arraylike::{arraylike, PyArrayLike, PyArrayLike2};
 
 #[pyfunction]
 fn sum_of_rows<'py>(py: Python<'py>, ar: PyArrayLike2<'py, u32>) -> Bound<'py, PyArray1<u32>> {
     ar.view().sum_axis(Axis(0)).into_pyarray_bound(py)
 }
 
+#[arraylike]
+#[pyfunction]
+fn sum_of_rows_view<'py>(py: Python<'py>, ar: &ArrayView2<u32>) -> Bound<'py, PyArray1<u32>> {
+    ar.sum_axis(Axis(0)).into_pyarray_bound(py)
+}
+
+#[pyfunction]
+fn normalize_rows<'py>(py: Python<'py>, ar: PyArrayLike2<'py, f64>) -> PyArrayLike2<'py, f64> {
+    let view = ar.view();
+    let norms = view.map_axis(Axis(1), |row| row.dot(&row).sqrt());
+    PyArrayLike::from_owned(&view / &norms.insert_axis(Axis(1)), py)
+}
+
 #[pymodule]
 fn example(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(sum_of_rows, m)?)?;
+    m.add_function(wrap_pyfunction!(sum_of_rows_view, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_rows, m)?)?;
     Ok(())
 }
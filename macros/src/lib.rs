@@ -0,0 +1,144 @@
+//! This crate provides the [`arraylike`](macro@arraylike) attribute of `pyo3-arraylike`, which is re-exported there if
+//! the `macros` feature is enabled.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Error, FnArg, GenericArgument, ItemFn, Pat, PathArguments, Type,
+};
+
+/// Lets parameters of a `#[pyfunction]` be declared as array views, which are extracted via `PyArrayLike`.
+///
+/// The attribute has to be placed above `#[pyfunction]`. Every parameter of type `&ArrayView<T, D>`, or of one of the
+/// aliases `&ArrayView0<T>` to `&ArrayView6<T>` and `&ArrayViewD<T>`, is replaced by an array like, whose view is bound
+/// to the original name at the beginning of the body. Other parameters are left unchanged. E.g.
+///
+/// ```ignore
+/// #[arraylike]
+/// #[pyfunction]
+/// fn total(ar: &ArrayView2<f64>) -> f64 {
+///     ar.sum()
+/// }
+/// ```
+///
+/// expands to
+///
+/// ```ignore
+/// #[pyfunction]
+/// fn total<'arraylike>(ar: ::pyo3_arraylike::PyArrayLike2<'arraylike, f64>) -> f64 {
+///     let ar = ar.view();
+///     let ar: &ArrayView2<f64> = &ar;
+///     ar.sum()
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn arraylike(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return Error::new_spanned(attr, "`arraylike` does not take any arguments")
+            .to_compile_error()
+            .into();
+    }
+    let mut function = parse_macro_input!(item as ItemFn);
+    match expand(&mut function) {
+        Ok(()) => quote!(#function).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(function: &mut ItemFn) -> syn::Result<()> {
+    let mut bindings = Vec::new();
+    for input in &mut function.sig.inputs {
+        let FnArg::Typed(input) = input else {
+            continue;
+        };
+        let Some(array_like) = array_like_type(&input.ty)? else {
+            continue;
+        };
+        let Pat::Ident(pat) = &mut *input.pat else {
+            return Err(Error::new_spanned(
+                &input.pat,
+                "array view parameters have to be plain identifiers",
+            ));
+        };
+        // The view is immutable anyway, and the binding is shadowed at the beginning of the body.
+        pat.mutability = None;
+        let name = pat.ident.clone();
+        let ty = &input.ty;
+        bindings.push(quote! {
+            let #name = #name.view();
+            let #name: #ty = &#name;
+        });
+        *input.ty = array_like;
+    }
+    if bindings.is_empty() {
+        return Ok(());
+    }
+    function
+        .sig
+        .generics
+        .params
+        .insert(0, parse_quote!('arraylike));
+    let block = &function.block;
+    *function.block = parse_quote!({
+        #(#bindings)*
+        #block
+    });
+    Ok(())
+}
+
+/// Returns the array like to extract instead of `ty`, or `None` if `ty` is not a reference to an array view.
+fn array_like_type(ty: &Type) -> syn::Result<Option<Type>> {
+    let Type::Reference(reference) = ty else {
+        return Ok(None);
+    };
+    let Type::Path(path) = &*reference.elem else {
+        return Ok(None);
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return Ok(None);
+    };
+    let alias = match segment.ident.to_string().as_str() {
+        "ArrayView" => None,
+        "ArrayView0" => Some(quote!(PyArrayLike0)),
+        "ArrayView1" => Some(quote!(PyArrayLike1)),
+        "ArrayView2" => Some(quote!(PyArrayLike2)),
+        "ArrayView3" => Some(quote!(PyArrayLike3)),
+        "ArrayView4" => Some(quote!(PyArrayLike4)),
+        "ArrayView5" => Some(quote!(PyArrayLike5)),
+        "ArrayView6" => Some(quote!(PyArrayLike6)),
+        "ArrayViewD" => Some(quote!(PyArrayLikeDyn)),
+        _ => return Ok(None),
+    };
+    if reference.mutability.is_some() {
+        return Err(Error::new_spanned(
+            ty,
+            "array likes are read-only, so array view parameters cannot be mutable references",
+        ));
+    }
+    // Lifetime arguments of the view are replaced by the lifetime of the array like.
+    let types: Vec<&Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let expected = if alias.is_some() { 1 } else { 2 };
+    if types.len() != expected {
+        return Err(Error::new_spanned(
+            ty,
+            format!("expected {} type arguments of the array view", expected),
+        ));
+    }
+    Ok(Some(match alias {
+        Some(alias) => parse_quote!(::pyo3_arraylike::#alias<'arraylike, #(#types),*>),
+        None => parse_quote!(::pyo3_arraylike::PyArrayLike<'arraylike, #(#types),*>),
+    }))
+}
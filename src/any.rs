@@ -0,0 +1,103 @@
+use numpy::{
+    ndarray::Dimension,
+    npyffi::NPY_ARRAY_OWNDATA,
+    pyo3::{
+        exceptions::PyValueError,
+        types::{PyAnyMethods, PySequence},
+        Bound, FromPyObject, PyAny, PyResult,
+    },
+    Element, PyArrayDescr, PyArrayDescrMethods, PyUntypedArray, PyUntypedArrayMethods,
+};
+
+use crate::{ArrayLikeKind, PyArrayLike};
+
+macro_rules! any_array_like {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        /// An array like whose element type is chosen at runtime from the numpy dtype of the input.
+        ///
+        /// Inputs which are not numpy arrays are converted with `numpy.asarray` first, so that e.g. nested lists of
+        /// integers become [`I64`](Self::I64). The resulting array likes report the data as copied, unless numpy
+        /// borrows the buffer of the input. Other dtypes are rejected.
+        #[derive(Debug)]
+        pub enum AnyPyArrayLike<'py, D>
+        where
+            D: Dimension,
+        {
+            $(
+                #[doc = concat!("Elements of type `", stringify!($ty), "`.")]
+                $variant(PyArrayLike<'py, $ty, D>),
+            )*
+        }
+
+        impl<'py, D> FromPyObject<'py> for AnyPyArrayLike<'py, D>
+        where
+            D: Dimension + 'static,
+        {
+            fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+                let py = ob.py();
+                let (array, kind) = match ob.downcast::<PyUntypedArray>() {
+                    Ok(array) => (array.clone(), None),
+                    Err(_) => {
+                        let array = py
+                            .import_bound("numpy")?
+                            .call_method1("asarray", (ob,))?
+                            .downcast_into::<PyUntypedArray>()?;
+                        let kind = asarray_kind(ob, &array);
+                        (array, kind)
+                    }
+                };
+                // Arrays in non-native byte order are matched by their native equivalent and byte swapped on extraction.
+                let dtype = match array.dtype() {
+                    dtype if dtype.is_native_byteorder() == Some(false) => dtype
+                        .call_method1("newbyteorder", ("=",))?
+                        .downcast_into::<PyArrayDescr>()?,
+                    dtype => dtype,
+                };
+                $(
+                    if dtype.is_equiv_to(&<$ty>::get_dtype_bound(py)) {
+                        let array_like: PyArrayLike<'py, $ty, D> = array.extract()?;
+                        return Ok(Self::$variant(match kind {
+                            Some(kind) if array_like.is_zero_copy() => array_like.with_kind(kind),
+                            _ => array_like,
+                        }));
+                    }
+                )*
+                Err(PyValueError::new_err(format!(
+                    "Expected an array like of a numeric or boolean dtype, got dtype {}.",
+                    dtype
+                )))
+            }
+        }
+    };
+}
+
+/// Returns the provenance of the array created by `numpy.asarray` from `ob`, or `None` if it borrows the buffer of `ob`.
+fn asarray_kind(ob: &Bound<'_, PyAny>, array: &Bound<'_, PyUntypedArray>) -> Option<ArrayLikeKind> {
+    // SAFETY: The pointer refers to a live numpy array kept alive by `array`.
+    let flags = unsafe { (*array.as_array_ptr()).flags };
+    if flags & NPY_ARRAY_OWNDATA == 0 {
+        return None;
+    }
+    let kind = if array.ndim() == 0 {
+        ArrayLikeKind::Scalar
+    } else if ob.downcast::<PySequence>().is_ok() {
+        ArrayLikeKind::BuiltFromSequence
+    } else {
+        ArrayLikeKind::CopiedFromNdArray
+    };
+    Some(kind)
+}
+
+any_array_like! {
+    F64(f64),
+    F32(f32),
+    I64(i64),
+    I32(i32),
+    I16(i16),
+    I8(i8),
+    U64(u64),
+    U32(u32),
+    U16(u16),
+    U8(u8),
+    Bool(bool),
+}
@@ -0,0 +1,102 @@
+use std::{fmt::Debug, marker::PhantomData, ops::Deref};
+
+use numpy::{
+    ndarray::Dimension,
+    pyo3::{
+        types::{IntoPyDict, PyAnyMethods},
+        Bound, FromPyObject, PyAny, PyResult,
+    },
+    Element, PyArrayDescr,
+};
+
+use crate::{ExtractOptions, PyArrayLike};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Marker trait for the casting rule used by [`PyArrayLikeCast`].
+pub trait Casting: sealed::Sealed + Debug {
+    /// Whether values may be cast like numpy's `casting='unsafe'`.
+    const UNSAFE: bool;
+}
+
+/// Marker allowing safe casts only, which is the default behaviour of `PyArrayLike`.
+#[derive(Debug)]
+pub struct CastSafe;
+
+/// Marker allowing any cast, equivalent to numpy's `astype(..., casting='unsafe')`.
+#[derive(Debug)]
+pub struct CastUnsafe;
+
+impl sealed::Sealed for CastSafe {}
+impl sealed::Sealed for CastUnsafe {}
+
+impl Casting for CastSafe {
+    const UNSAFE: bool = false;
+}
+
+impl Casting for CastUnsafe {
+    const UNSAFE: bool = true;
+}
+
+/// An array like whose elements are cast according to the rule given by `C`.
+#[derive(Debug)]
+pub struct PyArrayLikeCast<'py, T, D, C>(PyArrayLike<'py, T, D>, PhantomData<C>)
+where
+    T: Element,
+    D: Dimension,
+    C: Casting;
+
+impl<'py, T, D, C> PyArrayLikeCast<'py, T, D, C>
+where
+    T: Element,
+    D: Dimension,
+    C: Casting,
+{
+    /// Consumes `self` and returns the underlying array like.
+    pub fn into_inner(self) -> PyArrayLike<'py, T, D> {
+        self.0
+    }
+}
+
+impl<'py, T, D, C> Deref for PyArrayLikeCast<'py, T, D, C>
+where
+    T: Element,
+    D: Dimension,
+    C: Casting,
+{
+    type Target = PyArrayLike<'py, T, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'py, T, D, C> FromPyObject<'py> for PyArrayLikeCast<'py, T, D, C>
+where
+    T: Element + FromPyObject<'py> + 'static,
+    D: Dimension + 'static,
+    C: Casting,
+{
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let options = ExtractOptions::new().unsafe_casting(C::UNSAFE);
+        let array = PyArrayLike::extract_with(ob, &options)?;
+        Ok(Self(array, PhantomData))
+    }
+}
+
+/// Array like whose elements are cast like numpy's `casting='unsafe'`, e.g. truncating floats to integers.
+pub type PyArrayLikeUnsafe<'py, T, D> = PyArrayLikeCast<'py, T, D, CastUnsafe>;
+
+/// Converts `ob` into a numpy array of the given dtype using `casting='unsafe'`.
+pub(crate) fn astype_unsafe<'py>(
+    ob: &Bound<'py, PyAny>,
+    dtype: Bound<'py, PyArrayDescr>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = ob.py();
+    let casting = [("casting", "unsafe")].into_py_dict_bound(py);
+    py.import_bound("numpy")?
+        .call_method1("asarray", (ob,))?
+        .call_method("astype", (dtype,), Some(&casting))
+}
@@ -0,0 +1,35 @@
+/// The conversion by which an array like has been extracted, as reported by
+/// [`PyArrayLike::extract_with_path`](crate::PyArrayLike::extract_with_path).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoercionPath {
+    /// A numpy array of the requested type and dimension, which is borrowed without copying.
+    DirectNumpy,
+    /// An array created from a numpy array of another dtype or byte order, or by numpy from the input, e.g. via DLPack,
+    /// `numpy.asarray` or by reading an HDF5 dataset.
+    ArrayProtocol,
+    /// A `memoryview` or `bytes` object, whose buffer is borrowed without copying.
+    Buffer,
+    /// A single scalar, extracted as an array of dimension 0 or, via
+    /// [`ExtractOptions::broadcast_scalars`](crate::ExtractOptions::broadcast_scalars), as an array of any dimension with
+    /// all axes of length 1.
+    ScalarBroadcast,
+    /// A sequence of scalars, copied into an array of dimension 1.
+    FlatSequence,
+    /// Nested sequences, copied into an array of dimension 2 or higher.
+    NestedSequence,
+}
+
+/// Where the data of an array like comes from, as returned by [`PyArrayLike::provenance`](crate::PyArrayLike::provenance).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayLikeKind {
+    /// Borrowed from a numpy array without copying, including arrays wrapping a buffer or a DLPack capsule.
+    BorrowedNdArray,
+    /// Copied from a numpy array, e.g. to cast its elements or to change its memory order.
+    CopiedFromNdArray,
+    /// Built from a sequence of Python objects.
+    BuiltFromSequence,
+    /// Built from a single scalar.
+    Scalar,
+    /// Created in Rust, e.g. via [`PyArrayLike::from_owned`](crate::PyArrayLike::from_owned).
+    Owned,
+}
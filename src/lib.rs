@@ -2,22 +2,83 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
+mod any;
+mod casting;
+mod coercion;
+mod masked;
+mod ops;
+mod options;
+mod order;
+mod owned;
+mod parallel;
+mod sequence;
+mod stream;
 #[cfg(test)]
 mod test;
+// Lets the paths generated by the `arraylike` attribute resolve in the tests of this crate.
+#[cfg(all(test, feature = "macros"))]
+extern crate self as pyo3_arraylike;
+mod validate;
 
-use ndarray::{Array, ArrayView, Axis, Ix0, Ix1, Ix2, Ix3, Ix4, Ix5, Ix6, IxDyn};
+pub use any::AnyPyArrayLike;
+pub use casting::{CastSafe, CastUnsafe, Casting, PyArrayLikeCast, PyArrayLikeUnsafe};
+pub use coercion::{ArrayLikeKind, CoercionPath};
+pub use masked::PyArrayLikeMasked;
+pub use ops::{extract_batch_into_pyarray_stack, interp, matmul_into_pyarray};
+pub use options::ExtractOptions;
+pub use order::{
+    COrder, Contiguity, FOrder, MemoryOrder, PyArrayLikeC, PyArrayLikeContiguous, PyArrayLikeF,
+};
+pub use owned::PyArrayLikeOwned;
+#[cfg(feature = "macros")]
+pub use pyo3_arraylike_macros::arraylike;
+pub use validate::{
+    extract_adjacency_matrix, extract_onehot, extract_probability_simplex, extract_time_series,
+    extract_weights,
+};
+
+use casting::astype_unsafe;
+use masked::{masked_array_error, masked_entries};
+use ndarray::{
+    iter::Iter, Array, ArrayView, ArrayView1, ArrayView2, ArrayViewD, ArrayViewMut, Axis, CowArray,
+    IndexLonger, IntoDimension, Ix0, Ix1, Ix2, Ix3, Ix4, Ix5, Ix6, IxDyn, NdIndex, RemoveAxis,
+    ShapeBuilder, Slice,
+};
 use numpy::{
     ndarray::Dimension,
+    npyffi::NPY_ARRAY_WRITEABLE,
     pyo3::{
-        exceptions::PyValueError, types::PyAnyMethods, Bound, FromPyObject, PyAny, PyResult, Python,
+        exceptions::PyValueError,
+        types::{
+            IntoPyDict, PyAnyMethods, PyBytes, PyList, PyMemoryView, PyString, PyTuple,
+            PyTypeMethods,
+        },
+        Bound, FromPyObject, IntoPy, PyAny, PyErr, PyObject, PyResult, Python,
     },
-    Element, IntoPyArray, PyArray, PyArrayMethods, PyReadonlyArray,
+    Element, IntoPyArray, PyArray, PyArrayDescr, PyArrayDescrMethods, PyArrayMethods,
+    PyReadonlyArray, PyReadwriteArray, PyUntypedArray, PyUntypedArrayMethods,
 };
-use std::fmt::Debug;
+use sequence::{Kind, Progress, SequenceBuilder};
+use std::{fmt::Debug, ops::Index};
 
 /// To be used for extracting an array from any Python object which can be regarded as an array of type `T` and dimension `D` in a reasonable way.
+///
+/// Choosing `T = Py<PyAny>` accepts numpy arrays of dtype `object` as well as nested sequences of arbitrary Python objects.
+///
+/// Element types with a structured dtype, i.e. user types implementing `Element` for a record dtype, are only extracted
+/// from numpy arrays of exactly that dtype.
+///
+/// Numpy arrays in non-native byte order, e.g. of dtype `>f8` on a little-endian machine, are byte swapped into a
+/// native copy, which is then extracted like any other numpy array.
+///
+/// Empty sequences yield empty arrays. The lengths of axes below an empty sequence are zero, e.g. `[]` is extracted as an
+/// array of shape `(0, 0)` into a `PyArrayLike2`, and like numpy as an array of shape `(0,)` into a `PyArrayLikeDyn`.
+///
+/// An array like is neither `Send` nor `Sync`: It is bound to the GIL lifetime `'py` and may hold a borrow of a numpy
+/// array, which is tracked per interpreter and must only be accessed while the GIL is held. Use
+/// [`detach`](Self::detach) to move the data to another thread, or [`unbind`](Self::unbind) to store it.
 #[derive(Debug)]
-pub struct PyArrayLike<'py, T, D>(ArrayLike<'py, T, D>)
+pub struct PyArrayLike<'py, T, D>(ArrayLike<'py, T, D>, ArrayLikeKind, usize)
 where
     T: Element,
     D: Dimension;
@@ -28,6 +89,7 @@ where
     D: Dimension,
 {
     PyRef(PyReadonlyArray<'py, T, D>),
+    PyMut(PyReadwriteArray<'py, T, D>),
     Owned(Array<T, D>, Python<'py>),
 }
 
@@ -39,6 +101,7 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::PyRef(py_array) => f.debug_tuple("PyRef").field(py_array).finish(),
+            Self::PyMut(py_array) => f.debug_tuple("PyMut").field(py_array).finish(),
             Self::Owned(array, _) => f.debug_tuple("Owned").field(array).finish(),
         }
     }
@@ -49,11 +112,134 @@ where
     T: Element,
     D: Dimension,
 {
+    /// Wraps an owned array without going through Python.
+    pub fn from_owned(array: Array<T, D>, py: Python<'py>) -> Self {
+        PyArrayLike::new(ArrayLike::Owned(array, py), ArrayLikeKind::Owned)
+    }
+
+    /// Creates an owned array of the given shape with every element set to `elem`.
+    pub fn from_elem<Sh>(shape: Sh, elem: T, py: Python<'py>) -> Self
+    where
+        Sh: ShapeBuilder<Dim = D>,
+    {
+        Self::from_owned(Array::from_elem(shape, elem), py)
+    }
+
     /// Consumes `self` and moves its data into an owned array.
     pub fn into_owned_array(self) -> Array<T, D> {
         match self.0 {
-            ArrayLike::PyRef(py_array) => py_array.to_owned_array(),
             ArrayLike::Owned(array, _) => array,
+            _ => parallel::to_owned(self.view(), self.2),
+        }
+    }
+
+    /// Consumes `self` and detaches its data from Python as an owned array, which can be sent to other threads, e.g.
+    /// inside of [`Python::allow_threads`].
+    ///
+    /// Borrowed numpy arrays are copied, while owned data is moved.
+    pub fn detach(self) -> Array<T, D> {
+        self.into_owned_array()
+    }
+
+    /// Consumes `self` and moves its data into an owned array which is contiguous in the given order.
+    ///
+    /// Owned data is only copied if it is not contiguous in that order already.
+    pub fn into_owned_array_with_order(self, order: MemoryOrder) -> Array<T, D> {
+        let array = self.into_contiguous(order);
+        match array.0 {
+            ArrayLike::Owned(array, _) => array,
+            array_like => {
+                let threshold = array.2;
+                parallel::to_contiguous(
+                    PyArrayLike(array_like, array.1, threshold).view(),
+                    order,
+                    threshold,
+                )
+            }
+        }
+    }
+
+    /// Copies the elements into a vector in logical, i.e. row-major, order regardless of the memory layout.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+
+    /// Copies the elements into a vector in column-major order regardless of the memory layout.
+    pub fn to_vec_f(&self) -> Vec<T> {
+        self.t().iter().cloned().collect()
+    }
+
+    /// Consumes `self` and returns its elements in row-major order.
+    ///
+    /// The buffer of owned data in standard layout is reused, any other data is copied.
+    pub fn into_vec(self) -> Vec<T> {
+        let array = match self.0 {
+            ArrayLike::Owned(array, _) if array.is_standard_layout() => array,
+            _ => return self.view().iter().cloned().collect(),
+        };
+        let (ptr, len) = (array.as_ptr(), array.len());
+        let mut vec = array.into_raw_vec();
+        if vec.len() != len {
+            // The array is a contiguous part of a larger buffer.
+            let offset = (ptr as usize - vec.as_ptr() as usize) / std::mem::size_of::<T>();
+            vec.truncate(offset + len);
+            vec.drain(..offset);
+        }
+        vec
+    }
+
+    /// Consumes `self` and returns its elements in row-major order together with its shape.
+    ///
+    /// Like [`into_vec`](Self::into_vec), the buffer of owned data in standard layout is moved out without copying, and
+    /// any other data is copied exactly once.
+    pub fn into_raw_vec_and_shape(self) -> (Vec<T>, Vec<usize>) {
+        let shape = self.shape().to_vec();
+        (self.into_vec(), shape)
+    }
+
+    /// Consumes `self` and returns its elements in row-major order together with its shape if this is possible without
+    /// copying, i.e. for owned data in standard layout.
+    ///
+    /// Any other data is returned unchanged as `Err`.
+    pub fn try_into_raw_vec_and_shape(self) -> Result<(Vec<T>, Vec<usize>), Self> {
+        match &self.0 {
+            ArrayLike::Owned(array, _) if array.is_standard_layout() => {
+                Ok(self.into_raw_vec_and_shape())
+            }
+            _ => Err(self),
+        }
+    }
+
+    /// Copies the data into `out`, which must have exactly the same shape, so that an allocation can be reused.
+    pub fn copy_into(&self, out: &mut Array<T, D>) -> PyResult<()> {
+        let view = self.view();
+        if out.shape() != view.shape() {
+            return Err(PyValueError::new_err(format!(
+                "Expected an output array of shape {:?}, got shape {:?}.",
+                view.shape(),
+                out.shape()
+            )));
+        }
+        out.assign(&view);
+        Ok(())
+    }
+
+    /// Like [`into_owned_array`](Self::into_owned_array), but releases the GIL while copying borrowed numeric data.
+    ///
+    /// The borrow keeps the numpy array alive during the copy, but Python threads writing to it in the meantime may
+    /// produce a partially updated copy. Arrays of Python objects are copied while holding the GIL.
+    pub fn into_owned_array_allow_threads(self) -> Array<T, D>
+    where
+        T: Sync,
+    {
+        match self.0 {
+            ArrayLike::Owned(array, _) => array,
+            _ if T::IS_COPY => {
+                let (view, threshold) = (self.view(), self.2);
+                self.py()
+                    .allow_threads(|| parallel::to_owned(view, threshold))
+            }
+            _ => self.into_owned_array(),
         }
     }
 
@@ -61,17 +247,541 @@ where
     pub fn into_pyarray(self) -> PyReadonlyArray<'py, T, D> {
         match self.0 {
             ArrayLike::PyRef(py_array) => py_array,
+            ArrayLike::PyMut(py_array) => {
+                let array = (**py_array).clone();
+                drop(py_array);
+                array.readonly()
+            }
             ArrayLike::Owned(array, py) => array.into_pyarray_bound(py).readonly(),
         }
     }
 
+    /// Returns the borrowed numpy array, or copies owned data into a new numpy array.
+    ///
+    /// The copy is not cached, so every call allocates a new array for owned data.
+    pub fn to_pyarray(&self) -> Bound<'py, PyArray<T, D>> {
+        match &self.0 {
+            ArrayLike::Owned(array, py) => PyArray::from_array_bound(*py, array),
+            _ => (**self.borrowed().expect("array like is borrowed")).clone(),
+        }
+    }
+
+    /// Consumes `self` and returns the numpy array the data is borrowed from.
+    ///
+    /// This is the extracted object itself for numpy arrays of the right type. For other inputs, e.g. buffers, DLPack
+    /// tensors or arrays cast during extraction, it is the numpy array created during the conversion. Owned data is
+    /// moved into a new, writeable numpy array.
+    pub fn into_bound_object(self) -> Bound<'py, PyAny> {
+        match self.borrowed() {
+            Some(py_array) => py_array.as_any().clone(),
+            None => {
+                let py = self.py();
+                self.into_owned_array().into_pyarray_bound(py).into_any()
+            }
+        }
+    }
+
+    /// Consumes `self` and splits it along `axis` into chunks of length `size`, returned as a list of numpy arrays.
+    ///
+    /// The last chunk is shorter if the axis length is not divisible by `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is out of bounds or `size` is zero.
+    pub fn into_chunk_pyarrays(self, axis: Axis, size: usize) -> Bound<'py, PyList> {
+        let py = self.py();
+        let view = self.view();
+        let chunks = view
+            .axis_chunks_iter(axis, size)
+            .map(|chunk| chunk.to_owned().into_pyarray_bound(py));
+        PyList::new_bound(py, chunks)
+    }
+
+    /// Returns whether the borrowed numpy array is writeable, or `None` if the data is owned.
+    pub fn source_writeable(&self) -> Option<bool> {
+        self.borrowed().map(|py_array| {
+            // SAFETY: The pointer refers to a live numpy array kept alive by the borrow.
+            let flags = unsafe { (*py_array.as_array_ptr()).flags };
+            flags & NPY_ARRAY_WRITEABLE != 0
+        })
+    }
+
+    /// Returns the numpy dtype of the borrowed array, or the dtype corresponding to `T` for owned data.
+    ///
+    /// The dtype of a borrowed array may differ from the one of `T` in details like the byte order.
+    pub fn dtype(&self) -> Bound<'py, PyArrayDescr> {
+        match self.borrowed() {
+            Some(py_array) => py_array.dtype(),
+            None => T::get_dtype_bound(self.py()),
+        }
+    }
+
+    /// Returns numpy's kind character of the array's dtype, e.g. `'f'` for floating point or `'i'` for signed integer types.
+    pub fn dtype_kind(&self) -> char {
+        self.dtype().kind() as char
+    }
+
+    /// Returns whether the data is borrowed from the numpy array it has been extracted from, so that no copy has been made.
+    pub fn is_zero_copy(&self) -> bool {
+        self.1 == ArrayLikeKind::BorrowedNdArray
+    }
+
+    /// Returns where the data comes from, which is determined at extraction.
+    pub fn provenance(&self) -> ArrayLikeKind {
+        self.1
+    }
+
     /// Return a read-only view of the array.
-    pub fn view(&self) -> ArrayView<T, D> {
+    pub fn view(&self) -> ArrayView<'_, T, D> {
         match &self.0 {
             ArrayLike::PyRef(py_array) => py_array.as_array(),
+            ArrayLike::PyMut(py_array) => py_array.as_array(),
             ArrayLike::Owned(array, _) => array.view(),
         }
     }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out of bounds.
+    ///
+    /// This is the non-panicking counterpart of indexing with `[]`. Indices refer to the logical layout shown by
+    /// [`view`](Self::view), regardless of the strides in memory.
+    pub fn get<I>(&self, index: I) -> Option<&T>
+    where
+        I: NdIndex<D>,
+    {
+        IndexLonger::get(&self.view(), index)
+    }
+
+    /// Returns an iterator over references to the elements in logical order, i.e. in the order of
+    /// [`view`](Self::view) regardless of the memory layout.
+    pub fn iter(&self) -> Iter<'_, T, D> {
+        self.view().into_iter()
+    }
+
+    /// Returns an iterator over the elements in logical order together with their indices.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (D::Pattern, &T)> {
+        ndarray::indices(self.view().raw_dim())
+            .into_iter()
+            .zip(self.iter())
+    }
+
+    /// Returns an iterator over chunks of `size` subviews along `axis`, where the last chunk is smaller if the length of
+    /// the axis is not a multiple of `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is out of bounds or `size` is zero.
+    pub fn axis_chunks_iter(
+        &self,
+        axis: Axis,
+        size: usize,
+    ) -> impl ExactSizeIterator<Item = ArrayView<'_, T, D>> + DoubleEndedIterator {
+        assert!(
+            size > 0,
+            "axis_chunks_iter requires a chunk size of at least 1"
+        );
+        let view = self.view();
+        let len = view.len_of(axis);
+        (0..len.div_ceil(size)).map(move |chunk| {
+            let start = chunk * size;
+            let end = usize::min(start + size, len);
+            let mut chunk = view.clone();
+            chunk.slice_axis_inplace(axis, Slice::from(start..end));
+            chunk
+        })
+    }
+
+    /// Returns a copy-on-write array borrowing the data, for code generic over borrowed and owned arrays.
+    pub fn as_cow(&self) -> CowArray<'_, T, D> {
+        CowArray::from(self.view())
+    }
+
+    /// Returns the data in standard (C) layout, borrowing it if it is contiguous already and copying it otherwise.
+    pub fn as_standard_layout(&self) -> CowArray<'_, T, D> {
+        let view = self.view();
+        if view.is_standard_layout() {
+            CowArray::from(view)
+        } else {
+            CowArray::from(parallel::to_contiguous(view, MemoryOrder::C, self.2))
+        }
+    }
+
+    /// Consumes `self` and calls `f` with a copy-on-write array, which owns the data if it was owned by `self`.
+    ///
+    /// Owned data is moved without copying. A borrowed numpy array stays borrowed while `f` runs, since the view must
+    /// not outlive the borrow.
+    pub fn into_cow<R>(self, f: impl FnOnce(CowArray<'_, T, D>) -> R) -> R {
+        match self.0 {
+            ArrayLike::Owned(array, _) => f(CowArray::from(array)),
+            array_like => f(CowArray::from(
+                PyArrayLike(array_like, self.1, self.2).view(),
+            )),
+        }
+    }
+
+    /// Returns the length of every axis.
+    pub fn shape(&self) -> &[usize] {
+        match &self.0 {
+            ArrayLike::Owned(array, _) => array.shape(),
+            _ => self.borrowed().expect("array like is borrowed").shape(),
+        }
+    }
+
+    /// Returns the length of `axis`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is out of bounds.
+    pub fn len_of(&self, axis: Axis) -> usize {
+        self.shape()[axis.index()]
+    }
+
+    /// Returns the distance between consecutive elements along every axis, counted in elements like in ndarray.
+    ///
+    /// The strides are those of [`view`](Self::view), so they are negative for axes in reversed order. Numpy's byte
+    /// strides are converted, which is why the strides are returned as a vector.
+    pub fn strides(&self) -> Vec<isize> {
+        self.view().strides().to_vec()
+    }
+
+    /// Returns the number of axes.
+    pub fn ndim(&self) -> usize {
+        self.shape().len()
+    }
+
+    /// Returns the total number of elements, like numpy's `size`.
+    ///
+    /// An array of dimension 0 holds a single element.
+    pub fn len(&self) -> usize {
+        self.shape().iter().product()
+    }
+
+    /// Returns whether the array has no elements, i.e. the length of at least one axis is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes occupied by the elements, not counting gaps between non-contiguous elements.
+    pub fn nbytes(&self) -> usize {
+        self.len() * std::mem::size_of::<T>()
+    }
+
+    /// Returns a read-only view with reversed axes, without copying.
+    pub fn t(&self) -> ArrayView<'_, T, D> {
+        self.view().reversed_axes()
+    }
+
+    /// Returns a mutable view if the data is borrowed from a writeable numpy array, or `None` for owned or read-only data
+    /// and for copies made during extraction.
+    ///
+    /// On the first call, the shared borrow of the numpy array is upgraded to an exclusive one, which is kept until `self`
+    /// is dropped, so that changes are written through to the numpy array. While it is held, extracting the same numpy
+    /// array again returns an error. If another shared borrow exists already, the upgrade fails and `None` is returned.
+    pub fn try_view_mut(&mut self) -> Option<ArrayViewMut<'_, T, D>> {
+        if matches!(self.0, ArrayLike::PyRef(_))
+            && self.is_zero_copy()
+            && self.source_writeable() == Some(true)
+        {
+            if let ArrayLike::PyRef(py_array) = self.take() {
+                let array = (*py_array).clone();
+                // The shared borrow has to be released before an exclusive one can be acquired.
+                drop(py_array);
+                self.0 = match array.try_readwrite() {
+                    Ok(py_array) => ArrayLike::PyMut(py_array),
+                    Err(_) => ArrayLike::PyRef(array.readonly()),
+                };
+            }
+        }
+        match &mut self.0 {
+            ArrayLike::PyMut(py_array) => Some(py_array.as_array_mut()),
+            _ => None,
+        }
+    }
+
+    /// Consumes `self` and returns exclusive access to the borrowed numpy array, so that changes are visible from Python.
+    ///
+    /// Owned data, copies made during extraction, read-only numpy arrays and numpy arrays borrowed elsewhere are
+    /// returned unchanged as `Err(self)`.
+    pub fn try_readwrite(self) -> Result<PyReadwriteArray<'py, T, D>, Self> {
+        // Writes to a copy made during extraction would never reach the caller's array.
+        if !self.is_zero_copy() || self.source_writeable() != Some(true) {
+            return Err(self);
+        }
+        match self.0 {
+            ArrayLike::PyRef(py_array) => {
+                let array = (*py_array).clone();
+                // The shared borrow has to be released before an exclusive one can be acquired.
+                drop(py_array);
+                array
+                    .try_readwrite()
+                    .map_err(|_| PyArrayLike(ArrayLike::PyRef(array.readonly()), self.1, self.2))
+            }
+            ArrayLike::PyMut(py_array) => Ok(py_array),
+            array_like => Err(PyArrayLike(array_like, self.1, self.2)),
+        }
+    }
+
+    /// Consumes `self` and returns exclusive access to a writeable numpy array, together with whether the data was copied.
+    ///
+    /// The borrowed numpy array is reused if [`try_readwrite`](Self::try_readwrite) succeeds, so that changes are
+    /// visible from Python. Otherwise the data is moved or copied into a new numpy array and `true` is returned.
+    pub fn into_readwrite(self) -> PyResult<(PyReadwriteArray<'py, T, D>, bool)> {
+        match self.try_readwrite() {
+            Ok(py_array) => Ok((py_array, false)),
+            Err(array_like) => {
+                let py = array_like.py();
+                let py_array = array_like.into_owned_array().into_pyarray_bound(py);
+                Ok((py_array.try_readwrite()?, true))
+            }
+        }
+    }
+
+    /// Returns whether the data is contiguous in row-major (C) order.
+    pub fn is_c_contiguous(&self) -> bool {
+        self.view().is_standard_layout()
+    }
+
+    /// Returns whether the data is contiguous in column-major (F) order.
+    pub fn is_f_contiguous(&self) -> bool {
+        self.view().t().is_standard_layout()
+    }
+
+    /// Returns the memory order in which the data is contiguous, or `None` if it is not contiguous.
+    ///
+    /// Data which is contiguous in both orders, e.g. of dimension 1, is reported as C order. The result agrees with
+    /// [`as_slice`](Self::as_slice) and [`as_slice_f`](Self::as_slice_f).
+    pub fn order(&self) -> Option<MemoryOrder> {
+        if self.is_c_contiguous() {
+            Some(MemoryOrder::C)
+        } else if self.is_f_contiguous() {
+            Some(MemoryOrder::F)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the underlying data as a slice if it is contiguous in standard (C) order.
+    pub fn as_slice(&self) -> Option<&[T]> {
+        self.view().to_slice()
+    }
+
+    /// Returns the underlying data as a slice in memory order if it is contiguous in column-major (F) order.
+    pub fn as_slice_f(&self) -> Option<&[T]> {
+        self.view().reversed_axes().to_slice()
+    }
+
+    /// Returns the underlying data as a slice in memory order if it is contiguous in either order.
+    pub fn as_slice_memory_order(&self) -> Option<&[T]> {
+        self.view().to_slice_memory_order()
+    }
+
+    /// Returns a read-only view of dynamic dimension, so that views of differently shaped arrays can be stored together.
+    pub fn as_dyn_view(&self) -> ArrayViewD<'_, T> {
+        self.view().into_dyn()
+    }
+
+    /// Returns a view of dimension 2 without copying, or an error if the array has a different dimension.
+    pub fn as_2d_view(&self) -> PyResult<ArrayView2<'_, T>> {
+        let view = self.view();
+        let ndim = view.ndim();
+        view.into_dimensionality::<Ix2>().map_err(|_| {
+            PyValueError::new_err(format!(
+                "Expected an array of dimension 2, got dimension {}.",
+                ndim
+            ))
+        })
+    }
+
+    /// Consumes `self` and converts it into an array like of dynamic dimension, without copying.
+    pub fn into_dyn(self) -> PyArrayLikeDyn<'py, T> {
+        self.into_dimensionality()
+            .expect("every array has a dynamic dimension")
+    }
+
+    /// Consumes `self` and converts it into an array like of dimension `D2` without copying, or returns an error if the
+    /// number of axes does not match.
+    pub fn into_dimensionality<D2: Dimension>(self) -> PyResult<PyArrayLike<'py, T, D2>> {
+        let ndim = self.view().ndim();
+        if D2::NDIM.is_some_and(|expected| expected != ndim) {
+            return Err(PyValueError::new_err(format!(
+                "Expected an array of dimension {}, got dimension {}.",
+                D2::NDIM.unwrap_or(ndim),
+                ndim
+            )));
+        }
+        let array_like = match self.0 {
+            ArrayLike::PyRef(py_array) => {
+                let array = py_array.as_any().clone();
+                drop(py_array);
+                ArrayLike::PyRef(array.downcast_into::<PyArray<T, D2>>()?.readonly())
+            }
+            ArrayLike::PyMut(py_array) => {
+                let array = py_array.as_any().clone();
+                // The exclusive borrow has to be released before the array can be borrowed with another dimension.
+                drop(py_array);
+                ArrayLike::PyMut(array.downcast_into::<PyArray<T, D2>>()?.try_readwrite()?)
+            }
+            ArrayLike::Owned(array, py) => ArrayLike::Owned(
+                array
+                    .into_dimensionality()
+                    .expect("number of axes has been checked"),
+                py,
+            ),
+        };
+        Ok(PyArrayLike(array_like, self.1, self.2))
+    }
+
+    /// Returns the GIL token the array like is bound to, e.g. to allocate result arrays.
+    pub fn py(&self) -> Python<'py> {
+        match &self.0 {
+            ArrayLike::PyRef(py_array) => py_array.py(),
+            ArrayLike::PyMut(py_array) => py_array.py(),
+            ArrayLike::Owned(_, py) => *py,
+        }
+    }
+
+    /// Returns the borrowed numpy array, or `None` if the data is owned.
+    fn borrowed(&self) -> Option<&PyReadonlyArray<'py, T, D>> {
+        match &self.0 {
+            ArrayLike::PyRef(py_array) => Some(py_array),
+            ArrayLike::PyMut(py_array) => Some(py_array),
+            ArrayLike::Owned(_, _) => None,
+        }
+    }
+
+    /// Takes the contents of `self`, leaving an empty owned array of the same dimension behind.
+    fn take(&mut self) -> ArrayLike<'py, T, D> {
+        let view = self.view();
+        let mut dim = view.raw_dim();
+        let empty = match dim.slice_mut().first_mut() {
+            Some(len) => {
+                *len = 0;
+                Array::from_shape_vec(dim, Vec::new()).expect("shape has no elements")
+            }
+            // Arrays without axes always have a single element.
+            None => view.to_owned(),
+        };
+        let py = self.py();
+        std::mem::replace(&mut self.0, ArrayLike::Owned(empty, py))
+    }
+
+    /// Creates an array like which copies data in parallel from the default threshold on.
+    fn new(array_like: ArrayLike<'py, T, D>, kind: ArrayLikeKind) -> Self {
+        PyArrayLike(array_like, kind, parallel::DEFAULT_PARALLEL_THRESHOLD)
+    }
+
+    fn with_kind(self, kind: ArrayLikeKind) -> Self {
+        PyArrayLike(self.0, kind, self.2)
+    }
+
+    fn with_parallel_threshold(self, threshold: usize) -> Self {
+        PyArrayLike(self.0, self.1, threshold)
+    }
+
+    fn into_contiguous(self, order: MemoryOrder) -> Self {
+        let is_contiguous = match order {
+            MemoryOrder::C => self.is_c_contiguous(),
+            MemoryOrder::F => self.is_f_contiguous(),
+        };
+        if is_contiguous {
+            return self;
+        }
+        let kind = match self.1 {
+            ArrayLikeKind::BorrowedNdArray => ArrayLikeKind::CopiedFromNdArray,
+            kind => kind,
+        };
+        let array = parallel::to_contiguous(self.view(), order, self.2);
+        PyArrayLike(ArrayLike::Owned(array, self.py()), kind, self.2)
+    }
+}
+
+impl<'py, T> PyArrayLike<'py, T, Ix1>
+where
+    T: Element,
+{
+    /// Creates a one-dimensional owned array from a vector without going through Python.
+    pub fn from_vec(vec: Vec<T>, py: Python<'py>) -> Self {
+        Self::from_owned(Array::from_vec(vec), py)
+    }
+}
+
+impl<T, D> PyArrayLike<'_, T, D>
+where
+    T: Element,
+    D: RemoveAxis,
+{
+    /// Returns an iterator over the subviews along `axis`, e.g. over the rows of a matrix for `Axis(0)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is out of bounds.
+    pub fn axis_iter(
+        &self,
+        axis: Axis,
+    ) -> impl ExactSizeIterator<Item = ArrayView<'_, T, D::Smaller>> + DoubleEndedIterator {
+        let view = self.view();
+        (0..view.len_of(axis)).map(move |index| view.clone().index_axis_move(axis, index))
+    }
+}
+
+impl<T> PyArrayLike<'_, T, Ix2>
+where
+    T: Element,
+{
+    /// Returns an iterator over the rows of the matrix.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = ArrayView1<'_, T>> + DoubleEndedIterator {
+        self.axis_iter(Axis(0))
+    }
+}
+
+impl<T> PyArrayLike<'_, T, Ix0>
+where
+    T: Element,
+{
+    /// Returns the single element.
+    pub fn item(&self) -> T
+    where
+        T: Copy,
+    {
+        *self.item_ref()
+    }
+
+    /// Returns a reference to the single element.
+    pub fn item_ref(&self) -> &T {
+        &self[()]
+    }
+
+    /// Consumes `self` and returns the single element, which is moved out of owned data.
+    pub fn into_item(self) -> T {
+        self.into_owned_array().into_scalar()
+    }
+}
+
+impl<T> PyArrayLike<'_, T, IxDyn>
+where
+    T: Element,
+{
+    /// Returns an iterator over the subviews along the first axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array has dimension 0.
+    pub fn outer_iter(
+        &self,
+    ) -> impl ExactSizeIterator<Item = ArrayViewD<'_, T>> + DoubleEndedIterator {
+        self.axis_iter(Axis(0))
+    }
+
+    /// Returns the single element of an array of any dimension whose size is exactly 1, like numpy's `item`.
+    pub fn try_item(&self) -> PyResult<T> {
+        let view = self.view();
+        match view.iter().next() {
+            Some(item) if view.len() == 1 => Ok(item.clone()),
+            _ => Err(PyValueError::new_err(format!(
+                "Expected an array of size 1, got size {}.",
+                view.len()
+            ))),
+        }
+    }
 }
 
 impl<'py, T, D> From<PyArrayLike<'py, T, D>> for PyReadonlyArray<'py, T, D>
@@ -84,6 +794,52 @@ where
     }
 }
 
+/// Allows returning array likes from `#[pyfunction]`s.
+///
+/// Borrowed data is returned as the numpy array it is borrowed from, see
+/// [`into_bound_object`](PyArrayLike::into_bound_object), while owned data is moved into a new, writeable numpy array.
+impl<T, D> IntoPy<PyObject> for PyArrayLike<'_, T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.into_bound_object().into_py(py)
+    }
+}
+
+/// Indexes elements like ndarray, e.g. `array[[i, j]]` for a matrix. Use [`get`](PyArrayLike::get) to handle
+/// indices which may be out of bounds.
+///
+/// # Panics
+///
+/// Panics if the index is out of bounds.
+impl<T, D, I> Index<I> for PyArrayLike<'_, T, D>
+where
+    T: Element,
+    D: Dimension,
+    I: NdIndex<D>,
+{
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        IndexLonger::index(&self.view(), index)
+    }
+}
+
+impl<'a, T, D> IntoIterator for &'a PyArrayLike<'_, T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<T, D> From<PyArrayLike<'_, T, D>> for Array<T, D>
 where
     T: Element,
@@ -99,39 +855,333 @@ where
     T: Element + FromPyObject<'py> + 'static,
     D: Dimension + 'static,
 {
-    fn from_python(ob: &Bound<'py, PyAny>) -> Option<Self> {
+    /// Extracts an array like from `ob`, converting it according to `options`.
+    #[inline]
+    pub fn extract_with(ob: &Bound<'py, PyAny>, options: &ExtractOptions) -> PyResult<Self> {
+        Self::extract_reporting(ob, options, None)
+    }
+
+    /// Extracts an array like from `ob` like [`extract_with`](Self::extract_with), and reports which conversion was used.
+    ///
+    /// This helps to find out why an input has been copied instead of borrowed. The path describes how the input was
+    /// read. A copy made afterwards to satisfy [`ExtractOptions::contiguous`] is not part of it, e.g. a Fortran-ordered
+    /// numpy array extracted in C order still reports [`CoercionPath::DirectNumpy`]. Use
+    /// [`is_zero_copy`](Self::is_zero_copy) to find out whether the result is borrowed.
+    pub fn extract_with_path(
+        ob: &Bound<'py, PyAny>,
+        options: &ExtractOptions,
+    ) -> PyResult<(Self, CoercionPath)> {
+        Self::extract_tracing(ob, options, None)
+    }
+
+    /// Returns whether `ob` is a numpy array which is borrowed without copying when extracted with the default options.
+    ///
+    /// This is the case exactly for numpy arrays of type `T` and dimension `D` without masked entries, which are not
+    /// borrowed mutably elsewhere. Nothing is converted, so this can be used to warn about large copies upfront.
+    ///
+    /// Only numpy arrays are covered. Buffers like `memoryview` and `bytes` as well as DLPack tensors are borrowed without
+    /// copying as well, see [`is_zero_copy`](Self::is_zero_copy), but this function returns `false` for them, since
+    /// predicting their conversion would require performing it.
+    pub fn would_borrow(ob: &Bound<'py, PyAny>) -> bool {
+        matches!(masked_entries(ob), Ok(None))
+            && ob
+                .downcast::<PyArray<T, D>>()
+                .is_ok_and(|array| array.try_readonly().is_ok())
+    }
+
+    /// Extracts an array like from `ob` and broadcasts it to `shape`, which allows scalars and arrays of compatible shape.
+    ///
+    /// Broadcasting follows numpy's rules, e.g. an array of shape `(3, 1)` is broadcast to `(3, 4)` by repeating its
+    /// column. The result is always owned.
+    pub fn extract_broadcast(
+        ob: &Bound<'py, PyAny>,
+        shape: impl IntoDimension<Dim = D>,
+    ) -> PyResult<Self> {
+        let shape = shape.into_dimension();
+        let array = ob.extract::<PyArrayLikeDyn<'py, T>>()?;
+        let view = array.view();
+        let Some(broadcast) = view.broadcast(shape.clone()) else {
+            return Err(PyValueError::new_err(format!(
+                "Cannot broadcast an array of shape {:?} to shape {:?}.",
+                view.shape(),
+                shape.slice()
+            )));
+        };
+        Ok(Self::from_owned(broadcast.to_owned(), ob.py()))
+    }
+
+    /// Extracts an array like from `ob` like [`extract`](PyAnyMethods::extract), calling `callback` periodically with the
+    /// number of elements converted so far.
+    ///
+    /// Returning `false` from `callback` cancels the extraction with a `KeyboardInterrupt`. Numpy arrays of the right
+    /// type are borrowed without calling `callback` at all.
+    pub fn from_python_with_callback(
+        ob: &Bound<'py, PyAny>,
+        mut callback: impl FnMut(usize) -> bool,
+    ) -> PyResult<Self> {
+        Self::extract_reporting(ob, &ExtractOptions::default(), Some(&mut callback))
+    }
+
+    #[inline]
+    fn extract_reporting<'a>(
+        ob: &Bound<'py, PyAny>,
+        options: &'a ExtractOptions,
+        progress: Option<Progress<'a>>,
+    ) -> PyResult<Self> {
+        Self::extract_tracing(ob, options, progress).map(|(array, _)| array)
+    }
+
+    fn extract_tracing<'a>(
+        ob: &Bound<'py, PyAny>,
+        options: &'a ExtractOptions,
+        progress: Option<Progress<'a>>,
+    ) -> PyResult<(Self, CoercionPath)> {
+        let (array, path) = match Self::from_python(ob, options, progress)? {
+            Some(converted) => converted,
+            None if options.uses_asarray_fallback() => (
+                Self::from_asarray(ob, options)?,
+                CoercionPath::ArrayProtocol,
+            ),
+            None => return Err(extraction_error::<T, D>(ob)),
+        };
+        let array = array.with_parallel_threshold(options.copy_threshold());
+        let array = match options.order() {
+            Some(order) => array.into_contiguous(order),
+            None => array,
+        };
+        Ok((array, path))
+    }
+
+    fn from_asarray(ob: &Bound<'py, PyAny>, options: &ExtractOptions) -> PyResult<Self> {
+        let py = ob.py();
+        let converted = py.import_bound("numpy").and_then(|numpy| {
+            let casting = [("casting", "safe")].into_py_dict_bound(py);
+            numpy.call_method1("asarray", (ob,))?.call_method(
+                "astype",
+                (T::get_dtype_bound(py),),
+                Some(&casting),
+            )
+        });
+        match converted {
+            Ok(array) => Self::from_python(&array, options, None)?
+                .map(|(array, _)| array.with_kind(ArrayLikeKind::CopiedFromNdArray))
+                .ok_or_else(|| extraction_error::<T, D>(ob)),
+            Err(cause) => {
+                let err = extraction_error::<T, D>(ob);
+                err.set_cause(py, Some(cause));
+                Err(err)
+            }
+        }
+    }
+
+    #[inline]
+    fn from_python<'a>(
+        ob: &Bound<'py, PyAny>,
+        options: &'a ExtractOptions,
+        progress: Option<Progress<'a>>,
+    ) -> PyResult<Option<(Self, CoercionPath)>> {
+        // Plain numpy arrays of the right type are by far the most common input, so they skip all other checks.
+        if ob.is_exact_instance_of::<PyUntypedArray>() {
+            if let Ok(array) = ob.downcast::<PyArray<T, D>>() {
+                return Ok(Some((
+                    PyArrayLike::new(
+                        ArrayLike::PyRef(array.try_readonly()?),
+                        ArrayLikeKind::BorrowedNdArray,
+                    ),
+                    CoercionPath::DirectNumpy,
+                )));
+            }
+        }
+        Self::convert(ob, options, progress)
+    }
+
+    fn convert<'a>(
+        ob: &Bound<'py, PyAny>,
+        options: &'a ExtractOptions,
+        progress: Option<Progress<'a>>,
+    ) -> PyResult<Option<(Self, CoercionPath)>> {
+        if masked_entries(ob)?.is_some() {
+            return Err(masked_array_error());
+        }
+
         if let Ok(array) = ob.downcast::<PyArray<T, D>>() {
-            return Some(PyArrayLike(ArrayLike::PyRef(array.readonly())));
+            return Ok(Some((
+                PyArrayLike::new(
+                    ArrayLike::PyRef(array.try_readonly()?),
+                    ArrayLikeKind::BorrowedNdArray,
+                ),
+                CoercionPath::DirectNumpy,
+            )));
+        }
+
+        if ob.hasattr("__cuda_array_interface__")? {
+            return Err(PyValueError::new_err(
+                "GPU arrays are not supported. Copy the data to host memory first, e.g. by calling `.cpu()` (PyTorch) or `.get()` (CuPy).",
+            ));
+        }
+
+        // `read_direct` distinguishes h5py datasets from other objects supporting `[...]`.
+        if options.uses_read_datasets() && ob.hasattr("read_direct")? && ob.hasattr("shape")? {
+            let data = ob.get_item(ob.py().Ellipsis())?;
+            let converted = Self::convert(&data, options, progress)?;
+            return Ok(converted.map(|(array, _)| {
+                let array = array.with_kind(ArrayLikeKind::CopiedFromNdArray);
+                (array, CoercionPath::ArrayProtocol)
+            }));
+        }
+
+        #[cfg(feature = "dlpack")]
+        if ob.hasattr("__dlpack__")? {
+            let array = ob
+                .py()
+                .import_bound("numpy")
+                .and_then(|numpy| numpy.call_method1("from_dlpack", (ob,)));
+            if let Ok(array) = array {
+                if let Ok(array) = array.downcast::<PyArray<T, D>>() {
+                    return Ok(Some((
+                        PyArrayLike::new(
+                            ArrayLike::PyRef(array.try_readonly()?),
+                            ArrayLikeKind::BorrowedNdArray,
+                        ),
+                        CoercionPath::ArrayProtocol,
+                    )));
+                }
+            }
+        }
+
+        if let Some(array) = buffer_array::<T, D>(ob) {
+            return Ok(Some((
+                PyArrayLike::new(
+                    ArrayLike::PyRef(array.try_readonly()?),
+                    ArrayLikeKind::BorrowedNdArray,
+                ),
+                CoercionPath::Buffer,
+            )));
+        }
+
+        // Arrays in non-native byte order never match `T`, so they are byte swapped into a native copy first.
+        if let Ok(array) = ob.downcast::<PyUntypedArray>() {
+            if array.dtype().is_native_byteorder() == Some(false) {
+                let native = array.dtype().call_method1("newbyteorder", ("=",))?;
+                let native = array.call_method1("astype", (native,))?;
+                let converted = Self::convert(&native, options, progress)?;
+                return Ok(converted.map(|(array, _)| {
+                    let array = array.with_kind(ArrayLikeKind::CopiedFromNdArray);
+                    (array, CoercionPath::ArrayProtocol)
+                }));
+            }
+        }
+
+        // Large arrays of another numeric dtype are cast in parallel instead of by numpy.
+        #[cfg(feature = "rayon")]
+        if let Ok(array) = ob.downcast::<PyUntypedArray>() {
+            if let Some(array) = parallel::cast(array, options.copy_threshold())? {
+                return Ok(Some((
+                    PyArrayLike::new(
+                        ArrayLike::Owned(array, ob.py()),
+                        ArrayLikeKind::CopiedFromNdArray,
+                    ),
+                    CoercionPath::ArrayProtocol,
+                )));
+            }
+        }
+
+        if options.uses_unsafe_casting() && ob.is_instance_of::<PyUntypedArray>() {
+            if let Ok(array) = astype_unsafe(ob, T::get_dtype_bound(ob.py())) {
+                if let Ok(array) = array.downcast::<PyArray<T, D>>() {
+                    return Ok(Some((
+                        PyArrayLike::new(
+                            ArrayLike::PyRef(array.try_readonly()?),
+                            ArrayLikeKind::CopiedFromNdArray,
+                        ),
+                        CoercionPath::ArrayProtocol,
+                    )));
+                }
+            }
+        }
+
+        if options.uses_str_as_utf8()
+            && ob.is_instance_of::<PyString>()
+            && T::get_dtype_bound(ob.py()).is_equiv_to(&u8::get_dtype_bound(ob.py()))
+        {
+            let bytes = ob.call_method1("encode", ("utf-8",))?;
+            return Self::convert(&bytes, options, progress);
+        }
+
+        // Records cannot be assembled from Python objects.
+        if T::get_dtype_bound(ob.py()).kind() == b'V' {
+            return Ok(None);
+        }
+
+        // Every Python object can be extracted into an object element, so nested sequences
+        // have to be unpacked before trying the scalar path when the dimension is unknown.
+        let nested_objects = D::NDIM.is_none()
+            && T::get_dtype_bound(ob.py()).kind() == b'O'
+            && (ob.is_instance_of::<PyList>()
+                || ob.is_instance_of::<PyTuple>()
+                || ob.is_instance_of::<PyUntypedArray>());
+
+        // Sequences are only scalars of dtype `object`, which avoids a failing extraction for every nested list.
+        let sequence_of_numbers =
+            Kind::of(ob) == Kind::Sequence && T::get_dtype_bound(ob.py()).kind() != b'O';
+
+        if matches!(D::NDIM, None | Some(0)) && !nested_objects && !sequence_of_numbers {
+            if let Some(value) = options.extract_scalar::<T>(ob) {
+                let res = Array::from_elem((), value).into_dimensionality().ok();
+                return Ok(res.map(|res| {
+                    let array = PyArrayLike::new(
+                        ArrayLike::Owned(res, ob.py()),
+                        copied_kind(ob, ArrayLikeKind::Scalar),
+                    );
+                    (array, CoercionPath::ScalarBroadcast)
+                }));
+            }
         }
 
-        if matches!(D::NDIM, None | Some(0)) {
-            if let Ok(value) = ob.extract::<T>() {
-                let res = Array::from_elem((), value).into_dimensionality().ok()?;
-                return Some(PyArrayLike(ArrayLike::Owned(res, ob.py())));
+        // Scalars, numpy scalars and arrays of dimension 0 are exactly the objects which cannot be iterated, apart from strings.
+        if options.uses_broadcast_scalars()
+            && D::NDIM.is_some_and(|ndim| ndim > 0)
+            && ob.iter().is_err()
+        {
+            if let Some(value) = options.extract_scalar::<T>(ob) {
+                let shape = vec![1; D::NDIM.unwrap_or(0)];
+                let res = Array::from_elem(shape, value).into_dimensionality().ok();
+                return Ok(res.map(|res| {
+                    let array = PyArrayLike::new(
+                        ArrayLike::Owned(res, ob.py()),
+                        copied_kind(ob, ArrayLikeKind::Scalar),
+                    );
+                    (array, CoercionPath::ScalarBroadcast)
+                }));
             }
         }
 
-        if matches!(D::NDIM, None | Some(1)) {
-            if let Ok(array) = ob.extract::<Vec<T>>() {
-                let res = Array::from_vec(array).into_dimensionality().ok()?;
-                return Some(PyArrayLike(ArrayLike::Owned(res, ob.py())));
+        if ob.is_instance_of::<PyString>() {
+            let mut err_text =
+                "Expected an array like, got 'str'. Strings are not split into characters."
+                    .to_owned();
+            if T::get_dtype_bound(ob.py()).is_equiv_to(&u8::get_dtype_bound(ob.py())) {
+                err_text
+                    .push_str(" Use `ExtractOptions::str_as_utf8` to extract its UTF-8 encoding.");
             }
+            return Err(PyValueError::new_err(err_text));
         }
 
-        let sub_arrays = ob
-            .iter()
-            .ok()?
-            .map(|item| {
-                item.ok()
-                    .and_then(|ob| <PyArrayLike<T, D::Smaller>>::from_python(&ob))
-            })
-            .collect::<Option<Vec<_>>>()?;
-        let sub_array_views = sub_arrays.iter().map(|x| x.view()).collect::<Vec<_>>();
-        let array = ndarray::stack(Axis(0), &sub_array_views)
-            .ok()?
-            .into_dimensionality()
-            .ok()?;
-        return Some(PyArrayLike(ArrayLike::Owned(array, ob.py())));
+        let array = SequenceBuilder::new(options, D::NDIM, progress)
+            .build(ob)?
+            .and_then(|array| array.into_dimensionality().ok());
+        Ok(array.map(|array| {
+            let path = if ob.is_instance_of::<PyUntypedArray>() {
+                CoercionPath::ArrayProtocol
+            } else if array.ndim() <= 1 {
+                CoercionPath::FlatSequence
+            } else {
+                CoercionPath::NestedSequence
+            };
+            let kind = copied_kind(ob, ArrayLikeKind::BuiltFromSequence);
+            let array = PyArrayLike::new(ArrayLike::Owned(array, ob.py()), kind);
+            (array, path)
+        }))
     }
 }
 
@@ -140,14 +1190,112 @@ where
     T: Element + FromPyObject<'py> + 'static,
     D: Dimension + 'static,
 {
+    #[inline]
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        Self::from_python(ob).ok_or_else(|| {
-            let dtype = T::get_dtype_bound(ob.py());
-            let err_text = match D::NDIM {
-                Some(dim) => format!("Expected an array like of dimension {} containing elements which can be safely casted to {}.", dim, dtype),
-                None => format!("Expected an array like of arbitrary dimension containing elements which can be safely casted to {}.", dtype)
-            };
-            PyValueError::new_err(err_text)})
+        Self::extract_with(ob, &ExtractOptions::default())
+    }
+}
+
+/// Wraps a `memoryview` or `bytes` object in a numpy array without copying, if its elements are of type `T`.
+///
+/// Numpy derives the dtype from the format of the buffer, or from `T` for bytes.
+pub(crate) fn buffer_array<'py, T, D>(ob: &Bound<'py, PyAny>) -> Option<Bound<'py, PyArray<T, D>>>
+where
+    T: Element,
+    D: Dimension,
+{
+    let py = ob.py();
+    let is_bytes = ob.is_instance_of::<PyBytes>()
+        && T::get_dtype_bound(py).is_equiv_to(&u8::get_dtype_bound(py));
+    if !is_bytes && !ob.is_instance_of::<PyMemoryView>() {
+        return None;
+    }
+    let numpy = py.import_bound("numpy").ok()?;
+    let array = if is_bytes {
+        numpy.call_method1("frombuffer", (ob, "uint8"))
+    } else {
+        numpy.call_method1("asarray", (ob,))
+    };
+    array.ok()?.downcast_into().ok()
+}
+
+/// Numpy arrays converted element by element are still regarded as copies of a numpy array.
+fn copied_kind(ob: &Bound<'_, PyAny>, kind: ArrayLikeKind) -> ArrayLikeKind {
+    if ob.is_instance_of::<PyUntypedArray>() {
+        ArrayLikeKind::CopiedFromNdArray
+    } else {
+        kind
+    }
+}
+
+fn extraction_error<T, D>(ob: &Bound<'_, PyAny>) -> PyErr
+where
+    T: Element,
+    D: Dimension,
+{
+    let dtype = T::get_dtype_bound(ob.py());
+    let type_name = ob
+        .get_type()
+        .name()
+        .map_or_else(|_| "object".to_owned(), |name| name.into_owned());
+    let mut err_text = match D::NDIM {
+        Some(dim) => format!("Expected an array like of dimension {} containing elements which can be safely casted to {}, got '{}'.", dim, dtype, type_name),
+        None => format!("Expected an array like of arbitrary dimension containing elements which can be safely casted to {}, got '{}'.", dtype, type_name)
+    };
+    let mut index = Vec::new();
+    if find_none(ob, &mut index) {
+        err_text.push_str(&format!(" Found None at index {:?}.", index));
+        if dtype.kind() == b'f' {
+            err_text.push_str(" Use `ExtractOptions::none_as_nan` to convert it to NaN.");
+        }
+    }
+    PyValueError::new_err(err_text)
+}
+
+/// Searches lists, tuples and object arrays for a `None` value and records the position of the first one in `index`.
+fn find_none(ob: &Bound<'_, PyAny>, index: &mut Vec<usize>) -> bool {
+    if ob.is_none() {
+        return true;
+    }
+    let is_container = ob.is_instance_of::<PyList>()
+        || ob.is_instance_of::<PyTuple>()
+        || ob
+            .downcast::<PyUntypedArray>()
+            .is_ok_and(|array| array.dtype().kind() == b'O');
+    if !is_container {
+        return false;
+    }
+    let Ok(iter) = ob.iter() else {
+        return false;
+    };
+    for (i, item) in iter.enumerate() {
+        let Ok(item) = item else {
+            return false;
+        };
+        index.push(i);
+        if find_none(&item, index) {
+            return true;
+        }
+        index.pop();
+    }
+    false
+}
+
+/// Serializes the array in the format of ndarray, i.e. its shape followed by its elements in row-major order.
+///
+/// Since there is no Python object to borrow from, serialized data is deserialized as an owned `Array<T, D>`.
+/// Serializing does not touch the Python interpreter, but the value itself is still tied to the GIL lifetime `'py`.
+#[cfg(feature = "serde")]
+impl<T, D> serde::Serialize for PyArrayLike<'_, T, D>
+where
+    T: Element + serde::Serialize,
+    D: Dimension + serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.view().serialize(serializer)
     }
 }
 
@@ -0,0 +1,104 @@
+use numpy::{
+    ndarray::{ArrayView, Dimension, Zip},
+    pyo3::{
+        exceptions::PyValueError,
+        sync::GILOnceCell,
+        types::{PyAnyMethods, PyType},
+        Bound, FromPyObject, Py, PyAny, PyErr, PyResult,
+    },
+    Element, PyUntypedArray,
+};
+
+use crate::PyArrayLike;
+
+/// An array like extracted from a numpy masked array, whose masked entries have to be filled before the data can be used.
+///
+/// Any other input is extracted like a [`PyArrayLike`] without masked entries.
+#[derive(Debug)]
+pub struct PyArrayLikeMasked<'py, T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    data: PyArrayLike<'py, T, D>,
+    mask: Option<PyArrayLike<'py, bool, D>>,
+}
+
+impl<'py, T, D> PyArrayLikeMasked<'py, T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    /// Returns the mask, which is `true` for masked entries, or `None` if no entry is masked.
+    pub fn mask(&self) -> Option<ArrayView<'_, bool, D>> {
+        self.mask.as_ref().map(PyArrayLike::view)
+    }
+
+    /// Consumes `self` and replaces all masked entries by `value`.
+    ///
+    /// The data is only copied if at least one entry is masked.
+    pub fn fill(self, value: T) -> PyArrayLike<'py, T, D> {
+        let Some(mask) = self.mask else {
+            return self.data;
+        };
+        let py = self.data.py();
+        let mut array = self.data.into_owned_array();
+        Zip::from(&mut array)
+            .and(&mask.view())
+            .for_each(|element, &masked| {
+                if masked {
+                    *element = value.clone();
+                }
+            });
+        PyArrayLike::from_owned(array, py)
+    }
+}
+
+impl<'py, T, D> FromPyObject<'py> for PyArrayLikeMasked<'py, T, D>
+where
+    T: Element + FromPyObject<'py> + 'static,
+    D: Dimension + 'static,
+{
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let Some(mask) = masked_entries(ob)? else {
+            return Ok(Self {
+                data: ob.extract()?,
+                mask: None,
+            });
+        };
+        Ok(Self {
+            data: ob.getattr("data")?.extract()?,
+            mask: Some(mask.extract()?),
+        })
+    }
+}
+
+/// The error for masked arrays with masked entries, which cannot be extracted as a [`PyArrayLike`].
+pub(crate) fn masked_array_error() -> PyErr {
+    PyValueError::new_err(
+        "Masked arrays are not supported since masked entries hold arbitrary data. Extract a `PyArrayLikeMasked` and call `fill` instead.",
+    )
+}
+
+/// Returns the mask of `ob` if it is a numpy masked array with at least one masked entry.
+pub(crate) fn masked_entries<'py>(ob: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    static MASKED_ARRAY: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+    if !ob.is_instance_of::<PyUntypedArray>() {
+        return Ok(None);
+    }
+    let py = ob.py();
+    // The type is looked up once, so that plain numpy arrays are ruled out without importing `numpy.ma` again.
+    let masked_array = MASKED_ARRAY.get_or_try_init(py, || {
+        let ty = py.import_bound("numpy.ma")?.getattr("MaskedArray")?;
+        PyResult::Ok(ty.downcast_into::<PyType>()?.unbind())
+    })?;
+    if !ob.is_instance(masked_array.bind(py))? {
+        return Ok(None);
+    }
+    let ma = py.import_bound("numpy.ma")?;
+    if !ma.call_method1("is_masked", (ob,))?.is_truthy()? {
+        return Ok(None);
+    }
+    ma.call_method1("getmaskarray", (ob,)).map(Some)
+}
@@ -0,0 +1,524 @@
+use ndarray::{s, Array, Array1, Array2, ArrayView1, Axis, Dimension, RemoveAxis};
+use std::{
+    cmp::Ordering,
+    ops::{Add, Div},
+};
+
+use num_traits::{Float, FromPrimitive, NumCast, Signed, ToPrimitive, Zero};
+use numpy::{
+    pyo3::{
+        exceptions::PyValueError, types::PyAnyMethods, Bound, FromPyObject, PyAny, PyResult, Python,
+    },
+    Element, IntoPyArray, PyArray, PyArray2,
+};
+
+use crate::{ArrayLike, PyArrayLike, PyArrayLike1, PyArrayLike2};
+
+/// Computes the matrix product `a · b` and returns it as a numpy array.
+///
+/// The inner dimensions are validated first. The GIL is released while the product is computed.
+pub fn matmul_into_pyarray<'py>(
+    py: Python<'py>,
+    a: &PyArrayLike2<'py, f64>,
+    b: &PyArrayLike2<'py, f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let (a, b) = (a.view(), b.view());
+    if a.ncols() != b.nrows() {
+        return Err(PyValueError::new_err(format!(
+            "Cannot multiply matrices of shapes {:?} and {:?}.",
+            a.dim(),
+            b.dim()
+        )));
+    }
+    let product = py.allow_threads(|| a.dot(&b));
+    Ok(product.into_pyarray_bound(py))
+}
+
+/// Extracts every item of the iterable `obs` and stacks them along a new first axis into a numpy array.
+///
+/// All items must have the same shape, and at least one item is required to determine it.
+pub fn extract_batch_into_pyarray_stack<'py, T, D>(
+    py: Python<'py>,
+    obs: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray<T, D::Larger>>>
+where
+    T: Element + FromPyObject<'py> + 'static,
+    D: Dimension + 'static,
+    D::Larger: RemoveAxis,
+{
+    let items = obs
+        .iter()?
+        .map(|item| item?.extract::<PyArrayLike<'py, T, D>>())
+        .collect::<PyResult<Vec<_>>>()?;
+    let stacked = PyArrayLike::stack(Axis(0), &items)?;
+    Ok(stacked.into_owned_array().into_pyarray_bound(py))
+}
+
+/// Linearly interpolates the function given by the points `(xp, fp)` at `x`, like `numpy.interp`.
+///
+/// `xp` must be strictly increasing and as long as `fp`. Values of `x` outside of `xp` are clamped to the endpoints,
+/// i.e. they yield the first or last value of `fp`. NaN values of `x` yield NaN.
+pub fn interp(
+    x: &PyArrayLike1<'_, f64>,
+    xp: &PyArrayLike1<'_, f64>,
+    fp: &PyArrayLike1<'_, f64>,
+) -> PyResult<Array1<f64>> {
+    let (xp, fp) = (xp.view(), fp.view());
+    if xp.len() != fp.len() {
+        return Err(PyValueError::new_err(format!(
+            "Expected xp and fp of equal length, got {} and {}.",
+            xp.len(),
+            fp.len()
+        )));
+    }
+    if xp.is_empty() {
+        return Err(PyValueError::new_err(
+            "Expected at least one point to interpolate.",
+        ));
+    }
+    if let Some(index) = xp
+        .windows(2)
+        .into_iter()
+        .position(|pair| pair[0].partial_cmp(&pair[1]) != Some(Ordering::Less))
+    {
+        return Err(PyValueError::new_err(format!(
+            "Expected strictly increasing xp, but the value at index {} does not exceed its predecessor.",
+            index + 1
+        )));
+    }
+    let xp = xp.as_standard_layout();
+    let xp = xp.as_slice().expect("standard layout is contiguous");
+    let last = xp.len() - 1;
+    Ok(x.view().mapv(|x| {
+        // NaN lies in neither direction of the range of `xp`, and yields NaN like in numpy.
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if x <= xp[0] {
+            return fp[0];
+        }
+        if x >= xp[last] {
+            return fp[last];
+        }
+        // The first point not below `x`, which lies within `1..=last` for `x` strictly inside the range of `xp`.
+        let upper = xp.partition_point(|&xp| xp < x).clamp(1, last);
+        let lower = upper - 1;
+        let t = (x - xp[lower]) / (xp[upper] - xp[lower]);
+        fp[lower] + t * (fp[upper] - fp[lower])
+    }))
+}
+
+impl<'py, T> PyArrayLike1<'py, T>
+where
+    T: Element,
+{
+    /// Returns an iterator over all windows of `size` consecutive elements, from the first to the last window.
+    ///
+    /// The iterator is empty if `size` exceeds the length of the array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn windows(
+        &self,
+        size: usize,
+    ) -> impl ExactSizeIterator<Item = ArrayView1<'_, T>> + DoubleEndedIterator {
+        assert!(size > 0, "windows requires a window size of at least 1");
+        let view = self.view();
+        let count = (view.len() + 1).saturating_sub(size);
+        (0..count).map(move |start| view.slice_move(s![start..start + size]))
+    }
+
+    /// Applies `f` to every window of `window` consecutive elements, from the first to the last window.
+    ///
+    /// Fails if `window` is zero or exceeds the length of the array.
+    pub fn rolling_reduce<B, F>(&self, window: usize, f: F) -> PyResult<Vec<B>>
+    where
+        F: Fn(ArrayView1<'_, T>) -> B,
+    {
+        let view = self.view();
+        if window == 0 || window > view.len() {
+            return Err(PyValueError::new_err(format!(
+                "Expected a window between 1 and the array length {}, got {}.",
+                view.len(),
+                window
+            )));
+        }
+        Ok(view.windows(window).into_iter().map(f).collect())
+    }
+
+    /// Returns the elements for which `mask` is `true`, like `a[mask]` in numpy.
+    pub fn select(&self, mask: &PyArrayLike1<'py, bool>) -> PyResult<Self> {
+        let (view, mask) = (self.view(), mask.view());
+        if view.len() != mask.len() {
+            return Err(PyValueError::new_err(format!(
+                "Expected a mask of length {}, got length {}.",
+                view.len(),
+                mask.len()
+            )));
+        }
+        let selected = view
+            .iter()
+            .zip(mask)
+            .filter(|(_, &selected)| selected)
+            .map(|(element, _)| element.clone())
+            .collect::<Vec<_>>();
+        Ok(PyArrayLike::from_vec(selected, self.py()))
+    }
+}
+
+fn check_axis(axis: Axis, ndim: usize) -> PyResult<()> {
+    if axis.index() >= ndim {
+        return Err(PyValueError::new_err(format!(
+            "Axis {} is out of bounds for a result of dimension {}.",
+            axis.index(),
+            ndim
+        )));
+    }
+    Ok(())
+}
+
+impl PyArrayLike2<'_, f64> {
+    /// Computes the symmetric matrix of Euclidean distances between all pairs of rows.
+    ///
+    /// The GIL is released during the computation, like in [`matmul_into_pyarray`]. The borrow keeps a numpy array alive
+    /// in the meantime, but Python threads writing to it may affect the result.
+    pub fn pairwise_euclidean(&self) -> Array2<f64> {
+        let view = self.view();
+        let compute = || {
+            let n = view.nrows();
+            let mut distances = Array2::zeros((n, n));
+            for i in 0..n {
+                for j in i + 1..n {
+                    let distance = view
+                        .row(i)
+                        .iter()
+                        .zip(view.row(j))
+                        .map(|(a, b)| (a - b) * (a - b))
+                        .sum::<f64>()
+                        .sqrt();
+                    distances[[i, j]] = distance;
+                    distances[[j, i]] = distance;
+                }
+            }
+            distances
+        };
+        self.py().allow_threads(compute)
+    }
+}
+
+impl<'py, T, D> PyArrayLike<'py, T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    /// Stacks `arrays` of equal shape along a new axis inserted at position `axis`, like `numpy.stack`.
+    pub fn stack(axis: Axis, arrays: &[Self]) -> PyResult<PyArrayLike<'py, T, D::Larger>>
+    where
+        D::Larger: RemoveAxis,
+    {
+        let views = arrays.iter().map(PyArrayLike::view).collect::<Vec<_>>();
+        let Some(first) = views.first() else {
+            return Err(PyValueError::new_err(
+                "Expected at least one array to stack.",
+            ));
+        };
+        check_axis(axis, first.ndim() + 1)?;
+        if let Some((index, view)) = views
+            .iter()
+            .enumerate()
+            .find(|(_, view)| view.shape() != first.shape())
+        {
+            return Err(PyValueError::new_err(format!(
+                "Expected all arrays to have shape {:?}, got shape {:?} at index {}.",
+                first.shape(),
+                view.shape(),
+                index
+            )));
+        }
+        let stacked = ndarray::stack(axis, &views).expect("shapes have been validated");
+        Ok(PyArrayLike::from_owned(stacked, arrays[0].py()))
+    }
+
+    /// Joins `arrays` along the existing axis `axis`, like `numpy.concatenate`.
+    ///
+    /// The arrays must have equal lengths along all other axes.
+    pub fn concatenate(axis: Axis, arrays: &[Self]) -> PyResult<Self>
+    where
+        D: RemoveAxis,
+    {
+        let views = arrays.iter().map(PyArrayLike::view).collect::<Vec<_>>();
+        let Some(first) = views.first() else {
+            return Err(PyValueError::new_err(
+                "Expected at least one array to concatenate.",
+            ));
+        };
+        check_axis(axis, first.ndim())?;
+        let other_axes = |shape: &[usize]| {
+            let mut shape = shape.to_vec();
+            shape.remove(axis.index());
+            shape
+        };
+        if let Some((index, view)) = views
+            .iter()
+            .enumerate()
+            .find(|(_, view)| other_axes(view.shape()) != other_axes(first.shape()))
+        {
+            return Err(PyValueError::new_err(format!(
+                "Expected all arrays to match shape {:?} except along axis {}, got shape {:?} at index {}.",
+                first.shape(),
+                axis.index(),
+                view.shape(),
+                index
+            )));
+        }
+        let joined = ndarray::concatenate(axis, &views).expect("shapes have been validated");
+        Ok(PyArrayLike::from_owned(joined, arrays[0].py()))
+    }
+
+    /// Returns an owned array repeating the whole array `reps[i]` times along axis `i`, like `numpy.tile`.
+    ///
+    /// Fails if the length of `reps` differs from the number of axes.
+    pub fn tile(&self, reps: &[usize]) -> PyResult<Array<T, D>>
+    where
+        D: RemoveAxis,
+    {
+        let view = self.view();
+        if reps.len() != view.ndim() {
+            return Err(PyValueError::new_err(format!(
+                "Expected {} repetition counts, one per axis, got {}.",
+                view.ndim(),
+                reps.len()
+            )));
+        }
+        let mut tiled = view.to_owned();
+        for (axis, &count) in reps.iter().enumerate() {
+            let axis = Axis(axis);
+            tiled = if count == 0 {
+                tiled.slice_axis(axis, (..0).into()).to_owned()
+            } else {
+                let copies = vec![tiled.view(); count];
+                ndarray::concatenate(axis, &copies).expect("copies have equal shapes")
+            };
+        }
+        Ok(tiled)
+    }
+
+    /// Returns an owned copy of the array with the order of elements reversed along every axis, like `numpy.flip`.
+    pub fn flip(&self) -> Array<T, D> {
+        let mut view = self.view();
+        for axis in 0..view.ndim() {
+            view.invert_axis(Axis(axis));
+        }
+        view.as_standard_layout().into_owned()
+    }
+
+    /// Consumes `self` and converts every element into `U`, e.g. to accumulate `f32` input in `f64`.
+    ///
+    /// The result is always owned and has the same shape. No Python calls are made.
+    pub fn cast<U>(self) -> PyArrayLike<'py, U, D>
+    where
+        U: Element + From<T>,
+    {
+        let array = self.view().mapv(U::from);
+        PyArrayLike::from_owned(array, self.py())
+    }
+
+    /// Consumes `self` and converts every element into `U` like [`cast`](Self::cast), or returns an error if an element
+    /// cannot be represented, e.g. for narrowing integer casts.
+    pub fn try_cast<U>(self) -> PyResult<PyArrayLike<'py, U, D>>
+    where
+        T: ToPrimitive,
+        U: Element + NumCast,
+    {
+        let view = self.view();
+        let mut data = Vec::with_capacity(view.len());
+        for (index, x) in self.indexed_iter() {
+            let Some(x) = U::from(x.clone()) else {
+                return Err(PyValueError::new_err(format!(
+                    "Cannot cast the element at index {:?} into the target type.",
+                    index
+                )));
+            };
+            data.push(x);
+        }
+        let array = Array::from_shape_vec(view.raw_dim(), data)
+            .expect("shape matches the number of elements");
+        Ok(PyArrayLike::from_owned(array, self.py()))
+    }
+}
+
+impl<T, D> PyArrayLike<'_, T, D>
+where
+    T: Element + Float,
+    D: Dimension,
+{
+    /// Returns an owned copy of the array with every NaN replaced by `value`.
+    pub fn replace_nan(&self, value: T) -> Array<T, D> {
+        self.view().mapv(|x| if x.is_nan() { value } else { x })
+    }
+
+    /// Consumes `self` and moves its data into an owned array with every NaN replaced by `value`.
+    ///
+    /// Owned data is modified in place, while borrowed numpy arrays are copied with the replacement applied.
+    pub fn into_owned_array_nan_to(self, value: T) -> Array<T, D> {
+        match self.0 {
+            ArrayLike::Owned(mut array, _) => {
+                array.mapv_inplace(|x| if x.is_nan() { value } else { x });
+                array
+            }
+            _ => self.replace_nan(value),
+        }
+    }
+}
+
+impl<'py, T, D> PyArrayLike<'py, T, D>
+where
+    T: Element + Signed,
+    D: Dimension,
+{
+    /// Returns an owned copy of the array with the absolute value of every element.
+    pub fn abs(&self) -> PyArrayLike<'py, T, D> {
+        PyArrayLike::from_owned(self.view().mapv(|x| x.abs()), self.py())
+    }
+}
+
+impl<T, D> PyArrayLike<'_, T, D>
+where
+    T: Element + Zero + Add<Output = T>,
+    D: Dimension,
+{
+    /// Returns the sum of all elements, which is zero for an empty array.
+    pub fn sum(&self) -> T {
+        self.view().sum()
+    }
+}
+
+impl<T, D> PyArrayLike<'_, T, D>
+where
+    T: Element + Zero + FromPrimitive + Add<Output = T> + Div<Output = T>,
+    D: Dimension,
+{
+    /// Returns the arithmetic mean of all elements, or `None` if the array is empty.
+    pub fn mean(&self) -> Option<T> {
+        self.view().mean()
+    }
+}
+
+impl<'py, T, D> PyArrayLike<'py, T, D>
+where
+    T: Element + PartialOrd,
+    D: Dimension,
+{
+    /// Returns an owned copy of the array with every element limited to the range from `min` to `max`.
+    ///
+    /// Elements which cannot be compared, i.e. NaN, are kept.
+    pub fn clamp(&self, min: T, max: T) -> PyArrayLike<'py, T, D> {
+        let array = self.view().mapv(|x| {
+            if x < min {
+                min.clone()
+            } else if x > max {
+                max.clone()
+            } else {
+                x
+            }
+        });
+        PyArrayLike::from_owned(array, self.py())
+    }
+
+    /// Returns the smallest element, or `None` if the array is empty.
+    ///
+    /// NaN propagates like in `numpy.min`: If any element cannot be compared, i.e. is NaN, the first such element is
+    /// returned regardless of its position.
+    pub fn min(&self) -> Option<T> {
+        self.view()
+            .iter()
+            .reduce(|min, x| if is_nan(min) || x >= min { min } else { x })
+            .cloned()
+    }
+
+    /// Returns the largest element, or `None` if the array is empty.
+    ///
+    /// NaN propagates like in [`min`](Self::min).
+    pub fn max(&self) -> Option<T> {
+        self.view()
+            .iter()
+            .reduce(|max, x| if is_nan(max) || x <= max { max } else { x })
+            .cloned()
+    }
+}
+
+/// Returns whether `x` cannot even be compared with itself, which is the case exactly for NaN.
+fn is_nan<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+impl<T, D> PyArrayLike<'_, T, D>
+where
+    T: Element + Into<f64>,
+    D: Dimension,
+{
+    /// Computes the softmax along `axis`, subtracting the maximum of each lane before exponentiation for numerical stability.
+    ///
+    /// Fails if `axis` is out of bounds. An empty axis yields an empty result.
+    pub fn softmax(&self, axis: Axis) -> PyResult<Array<f64, D>> {
+        let view = self.view();
+        if axis.index() >= view.ndim() {
+            return Err(PyValueError::new_err(format!(
+                "Axis {} is out of bounds for an array of dimension {}.",
+                axis.index(),
+                view.ndim()
+            )));
+        }
+        let mut result = view.mapv(|x| x.into());
+        for mut lane in result.lanes_mut(axis) {
+            let max = lane.fold(f64::NEG_INFINITY, |max, &x| max.max(x));
+            lane.mapv_inplace(|x| (x - max).exp());
+            let sum = lane.sum();
+            lane.mapv_inplace(|x| x / sum);
+        }
+        Ok(result)
+    }
+
+    /// Computes the gradient along `axis` for samples with uniform `spacing`, like `numpy.gradient`.
+    ///
+    /// Interior points use central differences and the boundaries use one-sided differences.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is out of bounds or has fewer than two elements.
+    pub fn gradient(&self, axis: Axis, spacing: f64) -> Array<f64, D> {
+        let values = self.view().mapv(|x| x.into());
+        let len = values.len_of(axis);
+        assert!(
+            len >= 2,
+            "gradient requires at least two elements along the axis"
+        );
+        let mut gradient = Array::zeros(values.raw_dim());
+        for (mut target, lane) in gradient.lanes_mut(axis).into_iter().zip(values.lanes(axis)) {
+            target[0] = (lane[1] - lane[0]) / spacing;
+            for i in 1..len - 1 {
+                target[i] = (lane[i + 1] - lane[i - 1]) / (2.0 * spacing);
+            }
+            target[len - 1] = (lane[len - 1] - lane[len - 2]) / spacing;
+        }
+        gradient
+    }
+
+    /// Maps every element to the index of one of `levels` uniform bins covering `range`.
+    ///
+    /// Values outside of `range` are clamped to the first or last bin, and NaN is mapped to the first bin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is zero or the range is empty.
+    pub fn quantize(&self, levels: usize, range: (f64, f64)) -> Array<u32, D> {
+        let (low, high) = range;
+        assert!(levels > 0, "quantize requires at least one level");
+        assert!(low < high, "quantize requires a non-empty range");
+        let scale = levels as f64 / (high - low);
+        let last = (levels - 1) as f64;
+        self.view()
+            .mapv(|x| ((x.into() - low) * scale).floor().clamp(0.0, last) as u32)
+    }
+}
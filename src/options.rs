@@ -0,0 +1,172 @@
+use numpy::{
+    pyo3::{
+        types::{PyAnyMethods, PyFloat},
+        Bound, FromPyObject, PyAny,
+    },
+    Element, PyArray0, PyArrayDescrMethods, PyArrayMethods,
+};
+
+use crate::{casting::astype_unsafe, parallel::DEFAULT_PARALLEL_THRESHOLD, MemoryOrder};
+
+/// Options controlling how [`PyArrayLike::extract_with`](crate::PyArrayLike::extract_with) converts Python objects into arrays.
+///
+/// The default options are the ones used when extracting a `PyArrayLike` via `FromPyObject`.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    none_as_nan: bool,
+    order: Option<MemoryOrder>,
+    asarray_fallback: bool,
+    unsafe_casting: bool,
+    str_as_utf8: bool,
+    read_datasets: bool,
+    broadcast_scalars: bool,
+    #[cfg(feature = "rayon")]
+    parallel_threshold: Option<usize>,
+}
+
+impl ExtractOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts `None` values to `NaN` if the element type is a floating point type.
+    ///
+    /// This applies to nested sequences as well as to numpy arrays of dtype `object`. Integer element types always reject `None`.
+    pub fn none_as_nan(mut self, none_as_nan: bool) -> Self {
+        self.none_as_nan = none_as_nan;
+        self
+    }
+
+    /// Requires the extracted array to be contiguous in the given memory order.
+    ///
+    /// Numpy arrays in a different layout are copied into an owned array of the requested order. Nested lists and tuples
+    /// are built in the requested order directly.
+    pub fn contiguous(mut self, order: MemoryOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Calls `numpy.asarray` as a last resort if none of the built-in conversions apply.
+    ///
+    /// The result is cast safely to the element type. Exceptions raised by numpy are attached as the cause of the extraction error.
+    /// This is off by default since it may hide bugs by accepting almost anything numpy can convert.
+    pub fn asarray_fallback(mut self, asarray_fallback: bool) -> Self {
+        self.asarray_fallback = asarray_fallback;
+        self
+    }
+
+    /// Casts elements like numpy's `casting='unsafe'` if they cannot be converted safely.
+    ///
+    /// Numpy arrays are converted via `astype`, and scalars in nested sequences are converted one by one, so floats are
+    /// truncated and out-of-range integers wrap around exactly as in numpy.
+    pub fn unsafe_casting(mut self, unsafe_casting: bool) -> Self {
+        self.unsafe_casting = unsafe_casting;
+        self
+    }
+
+    /// Extracts a string as its UTF-8 encoded bytes if the element type is `u8`.
+    ///
+    /// This applies to strings given directly, which yield a one-dimensional array. Strings are rejected otherwise.
+    pub fn str_as_utf8(mut self, str_as_utf8: bool) -> Self {
+        self.str_as_utf8 = str_as_utf8;
+        self
+    }
+
+    /// Reads HDF5 datasets, i.e. `h5py.Dataset` objects, completely into memory via `dataset[...]` before extraction.
+    ///
+    /// This is off by default since the whole dataset is loaded at once, which may exhaust memory for large files.
+    /// Without this option, datasets are iterated row by row, which is very slow.
+    pub fn read_datasets(mut self, read_datasets: bool) -> Self {
+        self.read_datasets = read_datasets;
+        self
+    }
+
+    /// Extracts scalars, including numpy arrays of dimension 0, into arrays of any fixed dimension with all axes of length 1.
+    ///
+    /// E.g. `5.0` and `np.array(5.0)` become `[5.0]` in a `PyArrayLike1`, like `numpy.atleast_1d`. Without this option,
+    /// scalars are only extracted into arrays of dimension 0 or dynamic dimension.
+    pub fn broadcast_scalars(mut self, broadcast_scalars: bool) -> Self {
+        self.broadcast_scalars = broadcast_scalars;
+        self
+    }
+
+    /// Copies and converts numpy arrays of at least `threshold` elements using multiple threads.
+    ///
+    /// This applies to numpy arrays of another numeric dtype which can be cast safely into `T`, and to copies of
+    /// non-contiguous numpy arrays of plain numeric data, e.g. when requesting a memory order or calling
+    /// [`into_owned_array`](crate::PyArrayLike::into_owned_array) later on. Contiguous data is copied at once instead.
+    /// Python objects are always converted on the calling thread.
+    #[cfg(feature = "rayon")]
+    pub fn parallel_threshold(mut self, threshold: usize) -> Self {
+        self.parallel_threshold = Some(threshold);
+        self
+    }
+
+    pub(crate) fn uses_unsafe_casting(&self) -> bool {
+        self.unsafe_casting
+    }
+
+    pub(crate) fn uses_str_as_utf8(&self) -> bool {
+        self.str_as_utf8
+    }
+
+    pub(crate) fn uses_read_datasets(&self) -> bool {
+        self.read_datasets
+    }
+
+    pub(crate) fn uses_broadcast_scalars(&self) -> bool {
+        self.broadcast_scalars
+    }
+
+    pub(crate) fn uses_asarray_fallback(&self) -> bool {
+        self.asarray_fallback
+    }
+
+    pub(crate) fn order(&self) -> Option<MemoryOrder> {
+        self.order
+    }
+
+    pub(crate) fn copy_threshold(&self) -> usize {
+        #[cfg(feature = "rayon")]
+        if let Some(threshold) = self.parallel_threshold {
+            return threshold;
+        }
+        DEFAULT_PARALLEL_THRESHOLD
+    }
+
+    /// Extracts a single element, applying the configured conversions if a safe extraction is impossible.
+    pub(crate) fn extract_scalar<'py, T>(&self, ob: &Bound<'py, PyAny>) -> Option<T>
+    where
+        T: Element + FromPyObject<'py>,
+    {
+        ob.extract::<T>()
+            .ok()
+            .or_else(|| self.none_replacement(ob))
+            .or_else(|| self.cast_unsafe(ob))
+    }
+
+    fn none_replacement<'py, T>(&self, ob: &Bound<'py, PyAny>) -> Option<T>
+    where
+        T: Element + FromPyObject<'py>,
+    {
+        if !self.none_as_nan || !ob.is_none() || T::get_dtype_bound(ob.py()).kind() != b'f' {
+            return None;
+        }
+        PyFloat::new_bound(ob.py(), f64::NAN).extract().ok()
+    }
+
+    fn cast_unsafe<T>(&self, ob: &Bound<'_, PyAny>) -> Option<T>
+    where
+        T: Element,
+    {
+        // Iterables are never regarded as scalars, which also rules out 0-d arrays.
+        if !self.unsafe_casting || ob.iter().is_ok() {
+            return None;
+        }
+        let array = astype_unsafe(ob, T::get_dtype_bound(ob.py())).ok()?;
+        let array = array.downcast::<PyArray0<T>>().ok()?.try_readonly().ok()?;
+        let value = array.as_array()[()].clone();
+        Some(value)
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fmt::Debug, marker::PhantomData, ops::Deref};
+
+use numpy::{
+    ndarray::Dimension,
+    pyo3::{Bound, FromPyObject, PyAny, PyResult},
+    Element,
+};
+
+use crate::{ExtractOptions, PyArrayLike};
+
+/// Memory order of a contiguous array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryOrder {
+    /// Row-major order, as used by C.
+    C,
+    /// Column-major order, as used by Fortran.
+    F,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Marker trait for the memory order required by [`PyArrayLikeContiguous`].
+pub trait Contiguity: sealed::Sealed + Debug {
+    /// The required memory order.
+    const ORDER: MemoryOrder;
+}
+
+/// Marker requiring C-contiguous data.
+#[derive(Debug)]
+pub struct COrder;
+
+/// Marker requiring F-contiguous data.
+#[derive(Debug)]
+pub struct FOrder;
+
+impl sealed::Sealed for COrder {}
+impl sealed::Sealed for FOrder {}
+
+impl Contiguity for COrder {
+    const ORDER: MemoryOrder = MemoryOrder::C;
+}
+
+impl Contiguity for FOrder {
+    const ORDER: MemoryOrder = MemoryOrder::F;
+}
+
+/// An array like which is guaranteed to be contiguous in the memory order given by `O`.
+///
+/// Numpy arrays in the requested order are borrowed. Any other input is copied into an owned array of the requested order.
+#[derive(Debug)]
+pub struct PyArrayLikeContiguous<'py, T, D, O>(PyArrayLike<'py, T, D>, PhantomData<O>)
+where
+    T: Element,
+    D: Dimension,
+    O: Contiguity;
+
+impl<'py, T, D, O> PyArrayLikeContiguous<'py, T, D, O>
+where
+    T: Element,
+    D: Dimension,
+    O: Contiguity,
+{
+    /// Returns the underlying data as a slice in the memory order given by `O`.
+    pub fn as_slice(&self) -> &[T] {
+        self.0
+            .view()
+            .to_slice_memory_order()
+            .expect("array like is contiguous")
+    }
+
+    /// Consumes `self` and returns the underlying array like.
+    pub fn into_inner(self) -> PyArrayLike<'py, T, D> {
+        self.0
+    }
+}
+
+impl<'py, T, D, O> Deref for PyArrayLikeContiguous<'py, T, D, O>
+where
+    T: Element,
+    D: Dimension,
+    O: Contiguity,
+{
+    type Target = PyArrayLike<'py, T, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'py, T, D, O> FromPyObject<'py> for PyArrayLikeContiguous<'py, T, D, O>
+where
+    T: Element + FromPyObject<'py> + 'static,
+    D: Dimension + 'static,
+    O: Contiguity,
+{
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let options = ExtractOptions::new().contiguous(O::ORDER);
+        let array = PyArrayLike::extract_with(ob, &options)?;
+        Ok(Self(array, PhantomData))
+    }
+}
+
+/// Array like which is guaranteed to be C-contiguous.
+pub type PyArrayLikeC<'py, T, D> = PyArrayLikeContiguous<'py, T, D, COrder>;
+/// Array like which is guaranteed to be F-contiguous.
+pub type PyArrayLikeF<'py, T, D> = PyArrayLikeContiguous<'py, T, D, FOrder>;
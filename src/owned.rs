@@ -0,0 +1,69 @@
+use ndarray::{Array, ArrayView, Dimension};
+use numpy::{
+    pyo3::{Py, Python},
+    Element, IntoPyArray, PyArray, PyArrayMethods,
+};
+
+use crate::{ArrayLike, ArrayLikeKind, PyArrayLike};
+
+/// An array like which is independent of the GIL lifetime `'py`, so that it can be stored e.g. in a `#[pyclass]`.
+///
+/// Borrowed numpy arrays are kept alive without copying their data, while owned data is moved into a new numpy array,
+/// so that it can be shared by every [`bind`](Self::bind). The [provenance](PyArrayLike::provenance) is kept as well.
+/// Obtained via [`PyArrayLike::unbind`].
+#[derive(Debug)]
+pub struct PyArrayLikeOwned<T, D>(Py<PyArray<T, D>>, ArrayLikeKind, usize)
+where
+    T: Element,
+    D: Dimension;
+
+impl<T, D> PyArrayLikeOwned<T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    /// Attaches the array like to the GIL again by borrowing the numpy array, without copying any data.
+    ///
+    /// The array like reports the same provenance as the one it was unbound from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numpy array is currently borrowed mutably, e.g. via [`PyArrayLike::try_view_mut`].
+    pub fn bind<'py>(&self, py: Python<'py>) -> PyArrayLike<'py, T, D> {
+        let array_like = ArrayLike::PyRef(self.0.bind(py).readonly());
+        PyArrayLike(array_like, self.1, self.2)
+    }
+
+    /// Calls `f` with a read-only view of the array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numpy array is currently borrowed mutably, e.g. via [`PyArrayLike::try_view_mut`].
+    pub fn with_view<R>(&self, py: Python<'_>, f: impl FnOnce(ArrayView<'_, T, D>) -> R) -> R {
+        f(self.0.bind(py).readonly().as_array())
+    }
+
+    /// Consumes `self` and copies its data into an owned array.
+    pub fn into_owned_array(self, py: Python<'_>) -> Array<T, D> {
+        self.bind(py).into_owned_array()
+    }
+}
+
+impl<'py, T, D> PyArrayLike<'py, T, D>
+where
+    T: Element,
+    D: Dimension,
+{
+    /// Consumes `self` and detaches it from the GIL lifetime `'py` without copying any data.
+    pub fn unbind(self) -> PyArrayLikeOwned<T, D> {
+        let (kind, threshold) = (self.1, self.2);
+        let py_array = match self.borrowed() {
+            Some(py_array) => py_array.as_unbound().clone(),
+            None => {
+                let py = self.py();
+                self.into_owned_array().into_pyarray_bound(py).unbind()
+            }
+        };
+        PyArrayLikeOwned(py_array, kind, threshold)
+    }
+}
@@ -0,0 +1,195 @@
+use ndarray::{Array, ArrayView, Dimension};
+use numpy::Element;
+
+use crate::MemoryOrder;
+#[cfg(feature = "rayon")]
+use crate::PyArrayLike;
+#[cfg(feature = "rayon")]
+use ndarray::parallel::prelude::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use num_traits::AsPrimitive;
+#[cfg(feature = "rayon")]
+use numpy::{
+    pyo3::{types::PyAnyMethods, Bound, PyResult},
+    PyArray, PyArrayDescrMethods, PyArrayMethods, PyUntypedArray, PyUntypedArrayMethods,
+};
+#[cfg(feature = "rayon")]
+use std::any::{Any, TypeId};
+
+/// Number of elements from which copies are spread across threads unless configured otherwise.
+pub(crate) const DEFAULT_PARALLEL_THRESHOLD: usize = 1 << 16;
+
+/// Copies `view` into an owned array, keeping its memory order if it is contiguous.
+pub(crate) fn to_owned<T: Element, D: Dimension>(
+    view: ArrayView<'_, T, D>,
+    threshold: usize,
+) -> Array<T, D> {
+    // Contiguous data is copied as a whole, which is faster than any parallel copy.
+    if !is_parallel::<T>(view.len(), threshold) || view.as_slice_memory_order().is_some() {
+        view.to_owned()
+    } else if !view.is_standard_layout() && view.t().is_standard_layout() {
+        to_contiguous(view, MemoryOrder::F, threshold)
+    } else {
+        to_contiguous(view, MemoryOrder::C, threshold)
+    }
+}
+
+/// Copies `view` into an owned array which is contiguous in the given order.
+pub(crate) fn to_contiguous<T: Element, D: Dimension>(
+    view: ArrayView<'_, T, D>,
+    order: MemoryOrder,
+    threshold: usize,
+) -> Array<T, D> {
+    match order {
+        MemoryOrder::C => to_standard_layout(view, threshold),
+        MemoryOrder::F => to_standard_layout(view.reversed_axes(), threshold).reversed_axes(),
+    }
+}
+
+fn to_standard_layout<T: Element, D: Dimension>(
+    view: ArrayView<'_, T, D>,
+    threshold: usize,
+) -> Array<T, D> {
+    if view.is_standard_layout() {
+        view.to_owned()
+    } else if is_parallel::<T>(view.len(), threshold) {
+        par_to_standard_layout(view)
+    } else {
+        view.as_standard_layout().into_owned()
+    }
+}
+
+/// Only plain data is copied in parallel, since Python objects must stay on the thread holding the GIL.
+fn is_parallel<T: Element>(len: usize, threshold: usize) -> bool {
+    cfg!(feature = "rayon") && T::IS_COPY && len >= threshold
+}
+
+#[cfg(feature = "rayon")]
+fn par_to_standard_layout<T: Element, D: Dimension>(view: ArrayView<'_, T, D>) -> Array<T, D> {
+    /// Shares plain data between threads, which `Element` does not promise in general.
+    #[repr(transparent)]
+    struct Shared<T>(T);
+
+    // SAFETY: `Shared` is private to this function, which only creates it for `T::IS_COPY`, i.e. for plain numbers
+    // without interior mutability.
+    unsafe impl<T> Sync for Shared<T> {}
+
+    if !T::IS_COPY {
+        return view.as_standard_layout().into_owned();
+    }
+    // SAFETY: `Shared<T>` has the layout of `T`, and the new view borrows from the same data as `view`.
+    let source = unsafe { view.raw_view().cast::<Shared<T>>().deref_into_view() };
+    let mut array = Array::uninit(source.raw_dim());
+    ndarray::Zip::from(&mut array)
+        .and(&source)
+        .par_for_each(|target, source| {
+            target.write(source.0.clone());
+        });
+    // SAFETY: Every element has been written above.
+    unsafe { array.assume_init() }
+}
+
+#[cfg(not(feature = "rayon"))]
+fn par_to_standard_layout<T: Element, D: Dimension>(view: ArrayView<'_, T, D>) -> Array<T, D> {
+    view.as_standard_layout().into_owned()
+}
+
+/// Converts a numpy array of another numeric dtype into `T` using multiple threads, if numpy regards the cast as safe.
+///
+/// Returns `None` if `T` or the dtype of `array` is not a plain number type, if the cast is not safe or if the array has
+/// less than `threshold` elements, in which case numpy converts the array instead. Safe casts preserve every value, so
+/// the result is identical to numpy's.
+#[cfg(feature = "rayon")]
+pub(crate) fn cast<T, D>(
+    array: &Bound<'_, PyUntypedArray>,
+    threshold: usize,
+) -> PyResult<Option<Array<T, D>>>
+where
+    T: Element + 'static,
+    D: Dimension + 'static,
+{
+    if !is_parallel::<T>(array.len(), threshold) {
+        return Ok(None);
+    }
+    let py = array.py();
+    let target = T::get_dtype_bound(py);
+    let can_cast = py
+        .import_bound("numpy")?
+        .call_method1("can_cast", (array.dtype(), &target, "safe"))?
+        .is_truthy()?;
+    if !can_cast {
+        return Ok(None);
+    }
+
+    macro_rules! cast_from {
+        ($($source:ty),*) => {
+            $(
+                if array.dtype().is_equiv_to(&<$source>::get_dtype_bound(py)) {
+                    let Ok(array) = array.downcast::<PyArray<$source, D>>() else {
+                        return Ok(None);
+                    };
+                    let array = array.try_readonly()?;
+                    return Ok(cast_view::<$source, T, D>(array.as_array()));
+                }
+            )*
+        };
+    }
+    cast_from!(f64, f32, i64, i32, i16, i8, u64, u32, u16, u8);
+    Ok(None)
+}
+
+/// Casts every element of `view` into `T` in parallel, or returns `None` if `T` is not a plain number type.
+#[cfg(feature = "rayon")]
+pub(crate) fn cast_view<S, T, D>(view: ArrayView<'_, S, D>) -> Option<Array<T, D>>
+where
+    S: Copy + Sync + AsPrimitive<f64> + AsPrimitive<f32> + AsPrimitive<i64> + AsPrimitive<i32>,
+    S: AsPrimitive<i16> + AsPrimitive<i8> + AsPrimitive<u64> + AsPrimitive<u32> + AsPrimitive<u16>,
+    S: AsPrimitive<u8>,
+    T: 'static,
+    D: Dimension + 'static,
+{
+    fn par_cast<S, U, D>(view: ArrayView<'_, S, D>) -> Box<dyn Any>
+    where
+        S: AsPrimitive<U> + Sync,
+        U: Copy + Send + 'static,
+        D: Dimension + 'static,
+    {
+        let mut array = Array::uninit(view.raw_dim());
+        ndarray::Zip::from(&mut array)
+            .and(&view)
+            .par_for_each(|target, &source| {
+                target.write(source.as_());
+            });
+        // SAFETY: Every element has been written above.
+        Box::new(unsafe { array.assume_init() })
+    }
+
+    macro_rules! cast_into {
+        ($($target:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<$target>() {
+                    let array = par_cast::<S, $target, D>(view);
+                    return array.downcast::<Array<T, D>>().ok().map(|array| *array);
+                }
+            )*
+        };
+    }
+    cast_into!(f64, f32, i64, i32, i16, i8, u64, u32, u16, u8);
+    None
+}
+
+#[cfg(feature = "rayon")]
+impl<T, D> PyArrayLike<'_, T, D>
+where
+    T: Element + Sync,
+    D: Dimension,
+{
+    /// Returns a parallel iterator over references to all elements, in no particular order.
+    ///
+    /// The calling thread keeps holding the GIL while the iterator is consumed. Thus borrowed numpy data cannot be
+    /// modified by Python in the meantime, but the closures run on worker threads must not try to acquire the GIL, as this
+    /// would deadlock.
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = &T> {
+        self.view().into_par_iter()
+    }
+}
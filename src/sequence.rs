@@ -0,0 +1,408 @@
+use ndarray::{Array, Dimension, IxDyn, ShapeBuilder};
+use numpy::{
+    pyo3::{
+        exceptions::{PyKeyboardInterrupt, PyValueError},
+        types::{IntoPyDict, PyAnyMethods, PyComplex, PyFloat, PyList, PyLong, PyString, PyTuple},
+        Bound, FromPyObject, PyAny, PyResult,
+    },
+    Element, PyArray, PyArrayDescrMethods, PyArrayMethods, PyUntypedArray, PyUntypedArrayMethods,
+};
+
+use crate::{
+    casting::astype_unsafe,
+    masked::{masked_array_error, masked_entries},
+    ExtractOptions, MemoryOrder,
+};
+
+/// Callback receiving the number of elements converted so far, which cancels the conversion by returning `false`.
+pub(crate) type Progress<'a> = &'a mut dyn FnMut(usize) -> bool;
+
+/// Number of elements between two calls of a [`Progress`] callback.
+const PROGRESS_INTERVAL: usize = 1 << 16;
+
+/// Maximum number of elements reserved upfront based on a length hint, beyond which the buffer grows on demand.
+const MAX_RESERVED_ELEMENTS: usize = 1 << 16;
+
+/// Converts nested sequences into an array by discovering the shape while writing all elements into a single buffer.
+pub(crate) struct SequenceBuilder<'a, T> {
+    options: &'a ExtractOptions,
+    ndim: Option<usize>,
+    object_elements: bool,
+    shape: Vec<Option<usize>>,
+    index: Vec<usize>,
+    data: Vec<T>,
+    /// Element strides of a column-major result if its shape could be determined upfront.
+    f_strides: Option<Vec<usize>>,
+    progress: Option<Progress<'a>>,
+    converted: usize,
+}
+
+impl<'a, T> SequenceBuilder<'a, T>
+where
+    T: Element,
+{
+    /// Creates a builder for arrays of dimension `ndim`, or of any dimension if `ndim` is `None`.
+    pub(crate) fn new(
+        options: &'a ExtractOptions,
+        ndim: Option<usize>,
+        progress: Option<Progress<'a>>,
+    ) -> Self {
+        Self {
+            options,
+            ndim,
+            object_elements: false,
+            shape: Vec::new(),
+            index: Vec::new(),
+            data: Vec::new(),
+            f_strides: None,
+            progress,
+            converted: 0,
+        }
+    }
+
+    /// Builds an array from the elements of `ob`.
+    ///
+    /// Returns `Ok(None)` if `ob` cannot be regarded as an array of the requested type and dimension, and an error if the nested sequences are ragged.
+    pub(crate) fn build<'py>(mut self, ob: &Bound<'py, PyAny>) -> PyResult<Option<Array<T, IxDyn>>>
+    where
+        T: FromPyObject<'py>,
+    {
+        // Strings are iterable, but are never unpacked into their characters.
+        if Kind::of(ob) == Kind::Scalar {
+            return Ok(None);
+        }
+        self.object_elements = T::get_dtype_bound(ob.py()).kind() == b'O';
+        if self.options.order() == Some(MemoryOrder::F) {
+            self.probe_shape(ob);
+        }
+        if !self.fill_from_iter(ob, 0)? {
+            return Ok(None);
+        }
+        // Axes below empty sequences have not been reached, so their lengths are taken to be zero.
+        let ndim = self.ndim.unwrap_or(self.shape.len());
+        let mut shape: Vec<usize> = self.shape.iter().map(|len| len.unwrap_or(0)).collect();
+        shape.resize(ndim, 0);
+        if self.f_strides.is_some() {
+            Ok(Array::from_shape_vec(shape.f(), self.data).ok())
+        } else {
+            Ok(Array::from_shape_vec(shape, self.data).ok())
+        }
+    }
+
+    /// Determines the shape of nested lists and tuples from their first elements, so that elements can be written to
+    /// their column-major position directly. Ragged input is still detected while filling.
+    fn probe_shape(&mut self, ob: &Bound<'_, PyAny>) {
+        let Some(ndim) = self.ndim.filter(|&ndim| ndim >= 2) else {
+            return;
+        };
+        let mut shape = Vec::with_capacity(ndim);
+        let mut current = ob.clone();
+        for _ in 0..ndim {
+            if !current.is_instance_of::<PyList>() && !current.is_instance_of::<PyTuple>() {
+                return;
+            }
+            match current.len() {
+                Ok(len) if len > 0 => shape.push(len),
+                _ => return,
+            }
+            let Ok(first) = current.get_item(0) else {
+                return;
+            };
+            current = first;
+        }
+        let f_strides = shape
+            .iter()
+            .scan(1_usize, |stride, &len| {
+                let current = *stride;
+                *stride = stride.saturating_mul(len);
+                Some(current)
+            })
+            .collect();
+        self.shape = shape.into_iter().map(Some).collect();
+        self.f_strides = Some(f_strides);
+    }
+
+    /// Appends `value`, or writes it to its column-major position if the shape has been determined upfront.
+    fn push(&mut self, value: T) -> PyResult<()> {
+        if self.f_strides.is_none() {
+            self.data.push(value);
+        } else if let Some(offset) = self.f_offset(&[]) {
+            self.write_f(offset, value);
+        }
+        self.report(1)
+    }
+
+    /// Counts `count` converted elements and calls the progress callback whenever another interval has been completed.
+    fn report(&mut self, count: usize) -> PyResult<()> {
+        let Some(progress) = self.progress.as_mut() else {
+            return Ok(());
+        };
+        let previous = self.converted;
+        self.converted += count;
+        if self.converted / PROGRESS_INTERVAL > previous / PROGRESS_INTERVAL
+            && !progress(self.converted)
+        {
+            return Err(PyKeyboardInterrupt::new_err(format!(
+                "Extraction was cancelled after {} elements.",
+                self.converted
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the column-major offset of the element at `self.index` followed by `inner`.
+    ///
+    /// Positions outside of the probed shape belong to ragged sequences, which are reported once the sequence has been completed.
+    fn f_offset(&self, inner: &[usize]) -> Option<usize> {
+        let f_strides = self.f_strides.as_ref()?;
+        let mut offset = 0;
+        for ((&i, &len), &stride) in self
+            .index
+            .iter()
+            .chain(inner)
+            .zip(&self.shape)
+            .zip(f_strides)
+        {
+            if !matches!(len, Some(len) if i < len) {
+                return None;
+            }
+            offset += i * stride;
+        }
+        Some(offset)
+    }
+
+    fn write_f(&mut self, offset: usize, value: T) {
+        if self.data.is_empty() {
+            let len = self.shape.iter().map(|len| len.unwrap_or(0)).product();
+            self.data = vec![value.clone(); len];
+        }
+        self.data[offset] = value;
+    }
+
+    fn fill<'py>(&mut self, ob: &Bound<'py, PyAny>, depth: usize) -> PyResult<bool>
+    where
+        T: FromPyObject<'py>,
+    {
+        // Nested masked arrays are rejected like top-level ones, instead of reading the data behind masked entries.
+        if masked_entries(ob)?.is_some() {
+            return Err(masked_array_error());
+        }
+        if let Ok(array) = ob.downcast::<PyArray<T, IxDyn>>() {
+            if !matches!(self.ndim, Some(ndim) if ndim != depth + array.ndim()) {
+                return self.fill_from_array(array, depth);
+            }
+        }
+        if let Ok(array) = ob.downcast::<PyUntypedArray>() {
+            if !matches!(self.ndim, Some(ndim) if ndim != depth + array.ndim()) {
+                if let Some(array) = self.cast_array(array) {
+                    return self.fill_from_array(&array, depth);
+                }
+            }
+        }
+
+        let kind = Kind::of(ob);
+        let may_be_leaf = match self.ndim {
+            Some(ndim) => ndim == depth,
+            None => !self.is_object_container(ob),
+        } && (kind != Kind::Sequence || self.object_elements);
+        if may_be_leaf {
+            if let Some(value) = self.options.extract_scalar::<T>(ob) {
+                if self.ndim.is_none() {
+                    if self.shape.len() > depth {
+                        return Ok(false);
+                    }
+                    self.ndim = Some(depth);
+                }
+                self.push(value)?;
+                return Ok(true);
+            }
+            if self.ndim.is_some() {
+                return Ok(false);
+            }
+        }
+        if kind == Kind::Scalar {
+            return Ok(false);
+        }
+
+        self.fill_from_iter(ob, depth)
+    }
+
+    fn fill_from_iter<'py>(&mut self, ob: &Bound<'py, PyAny>, depth: usize) -> PyResult<bool>
+    where
+        T: FromPyObject<'py>,
+    {
+        if self.ndim.is_some_and(|ndim| depth >= ndim) {
+            return Ok(false);
+        }
+        let Ok(iter) = ob.iter() else {
+            return Ok(false);
+        };
+        if self.shape.len() <= depth {
+            self.shape.resize(depth + 1, None);
+        }
+        // Only the first sequence at each depth needs a hint, afterwards the buffer has been sized already.
+        let hint = match self.shape[depth] {
+            None => length_hint(ob),
+            Some(_) => None,
+        };
+        let mut len = 0;
+        for item in iter {
+            let Ok(item) = item else {
+                return Ok(false);
+            };
+            self.index.push(len);
+            let filled = self.fill(&item, depth + 1)?;
+            self.index.pop();
+            if !filled {
+                return Ok(false);
+            }
+            len += 1;
+            if len == 1 {
+                if let Some(hint) = hint {
+                    self.reserve_rows(depth, hint.saturating_sub(1));
+                }
+            }
+        }
+        self.check_len(depth, len)?;
+        Ok(true)
+    }
+
+    fn fill_from_array(
+        &mut self,
+        array: &Bound<'_, PyArray<T, IxDyn>>,
+        depth: usize,
+    ) -> PyResult<bool> {
+        let Ok(array) = array.try_readonly() else {
+            return Ok(false);
+        };
+        let view = array.as_array();
+        if self.ndim.is_none() {
+            if self.shape.len() > depth + view.ndim() {
+                return Ok(false);
+            }
+            self.ndim = Some(depth + view.ndim());
+        }
+        if self.shape.len() < depth + view.ndim() {
+            self.shape.resize(depth + view.ndim(), None);
+        }
+        for (axis, &len) in view.shape().iter().enumerate() {
+            self.index.resize(depth + axis, 0);
+            let checked = self.check_len(depth + axis, len);
+            self.index.truncate(depth);
+            checked?;
+        }
+        if self.f_strides.is_some() {
+            for (index, value) in view.indexed_iter() {
+                if let Some(offset) = self.f_offset(index.slice()) {
+                    self.write_f(offset, value.clone());
+                }
+            }
+        } else if let Some(slice) = view.as_slice() {
+            // Rows of exactly matching, contiguous numpy arrays are copied in bulk.
+            self.data.extend_from_slice(slice);
+        } else {
+            self.data.extend(view.iter().cloned());
+        }
+        self.report(view.len())?;
+        Ok(true)
+    }
+
+    /// Casts a numpy array of another dtype to `T` as a whole, so that nested arrays of differing dtypes are unified.
+    ///
+    /// Returns `None` if numpy does not allow the cast under the configured casting rule, in which case the elements
+    /// are converted one by one, e.g. integers which fit into a smaller integer type.
+    fn cast_array<'py>(
+        &self,
+        array: &Bound<'py, PyUntypedArray>,
+    ) -> Option<Bound<'py, PyArray<T, IxDyn>>> {
+        let py = array.py();
+        // Arrays of objects are unpacked element by element.
+        if self.object_elements || array.dtype().kind() == b'O' {
+            return None;
+        }
+        let dtype = T::get_dtype_bound(py);
+        let array = if self.options.uses_unsafe_casting() {
+            astype_unsafe(array, dtype).ok()?
+        } else {
+            let casting = [("casting", "safe")].into_py_dict_bound(py);
+            array.call_method("astype", (dtype,), Some(&casting)).ok()?
+        };
+        array.downcast_into().ok()
+    }
+
+    /// Reserves space for `rows` further items at `depth`, once the size of a single item is known.
+    fn reserve_rows(&mut self, depth: usize, rows: usize) {
+        let Some(ndim) = self.ndim else {
+            return;
+        };
+        let Some(row_size) = self.shape[depth + 1..ndim]
+            .iter()
+            .try_fold(1_usize, |size, &len| {
+                len.map(|len| size.saturating_mul(len))
+            })
+        else {
+            return;
+        };
+        // The hint may be wrong, so only a bounded amount is reserved and the buffer still grows on demand.
+        let elements = rows.saturating_mul(row_size).min(MAX_RESERVED_ELEMENTS);
+        let _ = self.data.try_reserve(elements);
+    }
+
+    fn check_len(&mut self, depth: usize, len: usize) -> PyResult<()> {
+        match self.shape[depth] {
+            None => {
+                self.shape[depth] = Some(len);
+                Ok(())
+            }
+            Some(expected) if expected == len => Ok(()),
+            Some(expected) => Err(PyValueError::new_err(format!(
+                "Expected a sequence of length {} at index {:?}, got length {}.",
+                expected, self.index, len
+            ))),
+        }
+    }
+
+    /// Nested lists, tuples and arrays of objects are unpacked rather than treated as single elements.
+    fn is_object_container(&self, ob: &Bound<'_, PyAny>) -> bool {
+        self.object_elements
+            && (ob.is_instance_of::<PyList>()
+                || ob.is_instance_of::<PyTuple>()
+                || ob.is_instance_of::<PyUntypedArray>())
+    }
+}
+
+/// Category of a Python object, determined once to decide how it is converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// Lists and tuples, which can only be a single element of dtype `object`.
+    Sequence,
+    /// Numbers and strings, which are never unpacked.
+    Scalar,
+    /// Anything else, e.g. numpy arrays, numpy scalars or generators, which may be an element or an iterable.
+    Other,
+}
+
+impl Kind {
+    pub(crate) fn of(ob: &Bound<'_, PyAny>) -> Self {
+        if ob.is_instance_of::<PyList>() || ob.is_instance_of::<PyTuple>() {
+            Self::Sequence
+        } else if ob.is_instance_of::<PyFloat>()
+            || ob.is_instance_of::<PyLong>()
+            || ob.is_instance_of::<PyComplex>()
+            || ob.is_instance_of::<PyString>()
+        {
+            Self::Scalar
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Returns the length of `ob` if it is a sized container, or its `__length_hint__` otherwise.
+fn length_hint(ob: &Bound<'_, PyAny>) -> Option<usize> {
+    ob.len().ok().or_else(|| {
+        ob.call_method0("__length_hint__")
+            .and_then(|hint| hint.extract())
+            .ok()
+    })
+}
@@ -0,0 +1,85 @@
+use ndarray::{Axis, Ix1};
+use numpy::{
+    pyo3::{
+        exceptions::PyValueError,
+        types::{PyAnyMethods, PyString},
+        Bound, FromPyObject, PyAny, PyResult,
+    },
+    Element, PyArray1, PyArrayMethods,
+};
+
+use crate::{
+    buffer_array,
+    masked::{masked_array_error, masked_entries},
+    ExtractOptions, PyArrayLike,
+};
+
+impl<'py, T> PyArrayLike<'py, T, Ix1>
+where
+    T: Element + FromPyObject<'py>,
+{
+    /// Passes the elements of `ob` to `visit` in chunks of `chunk_len` elements, without building the whole array.
+    ///
+    /// Numpy arrays of type `T`, as well as `memoryview` and `bytes` objects holding elements of type `T`, are chunked
+    /// without copying if they are contiguous. Any other iterable is extracted element by element with the same casting
+    /// rules as `PyArrayLike1`. The final chunk may be shorter. Like extracting a `PyArrayLike1`, this fails for strings
+    /// and for masked arrays with masked entries.
+    pub fn for_each_chunk(
+        ob: &Bound<'py, PyAny>,
+        chunk_len: usize,
+        mut visit: impl FnMut(&[T]) -> PyResult<()>,
+    ) -> PyResult<()> {
+        if chunk_len == 0 {
+            return Err(PyValueError::new_err("Expected a positive chunk length."));
+        }
+
+        if ob.is_instance_of::<PyString>() {
+            return Err(PyValueError::new_err(
+                "Expected an iterable of elements, got 'str'. Strings are not split into characters.",
+            ));
+        }
+        if masked_entries(ob)?.is_some() {
+            return Err(masked_array_error());
+        }
+
+        let array = match ob.downcast::<PyArray1<T>>() {
+            Ok(array) => Some(array.clone()),
+            Err(_) => buffer_array::<T, Ix1>(ob),
+        };
+        if let Some(array) = array {
+            let array = array.try_readonly()?;
+            let view = array.as_array();
+            if let Some(slice) = view.as_slice() {
+                return slice.chunks(chunk_len).try_for_each(visit);
+            }
+            let mut chunk = Vec::with_capacity(chunk_len);
+            for strided_chunk in view.axis_chunks_iter(Axis(0), chunk_len) {
+                chunk.clear();
+                chunk.extend(strided_chunk.iter().cloned());
+                visit(&chunk)?;
+            }
+            return Ok(());
+        }
+
+        let mut chunk = Vec::with_capacity(chunk_len);
+        let options = ExtractOptions::default();
+        for (index, item) in ob.iter()?.enumerate() {
+            let Some(value) = options.extract_scalar::<T>(&item?) else {
+                return Err(PyValueError::new_err(format!(
+                    "Expected an element which can be safely casted to {} at index {}.",
+                    T::get_dtype_bound(ob.py()),
+                    index
+                )));
+            };
+            chunk.push(value);
+            if chunk.len() == chunk_len {
+                visit(&chunk)?;
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            visit(&chunk)?;
+        }
+        Ok(())
+    }
+}
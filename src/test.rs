@@ -1,10 +1,23 @@
-use crate::{ArrayLike, PyArrayLike0, PyArrayLike1, PyArrayLike2, PyArrayLikeDyn};
-use ndarray::{array, Array0};
+use crate::{
+    extract_adjacency_matrix, extract_batch_into_pyarray_stack, extract_onehot,
+    extract_probability_simplex, extract_time_series, extract_weights, interp, matmul_into_pyarray,
+    AnyPyArrayLike, ArrayLike, ArrayLikeKind, CastSafe, CoercionPath, ExtractOptions, MemoryOrder,
+    PyArrayLike, PyArrayLike0, PyArrayLike1, PyArrayLike2, PyArrayLike3, PyArrayLikeC,
+    PyArrayLikeCast, PyArrayLikeDyn, PyArrayLikeF, PyArrayLikeMasked, PyArrayLikeOwned,
+    PyArrayLikeUnsafe,
+};
+use ndarray::{array, Array0, Axis, Ix1, Ix2, ShapeBuilder};
 use numpy::{
     get_array_module,
     pyo3::{types::IntoPyDict, PyAny, Python},
+    IntoPyArray, PyArray1, PyArray2, PyArrayDyn, PyArrayMethods,
+};
+use pyo3::{
+    exceptions::{PyKeyboardInterrupt, PyTypeError, PyZeroDivisionError},
+    types::{PyAnyMethods, PyCFunction, PyDict, PyList, PyListMethods},
+    Bound, Py,
 };
-use pyo3::{types::PyAnyMethods, Bound};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 fn eval<'py>(py: Python<'py>, code: &str) -> Bound<'py, PyAny> {
     py.eval_bound(
@@ -21,7 +34,7 @@ fn extract_reference() {
         let py_array = eval(py, "np.array([[1,2],[3,4]], dtype='float64')");
         let extracted_array = py_array.extract::<PyArrayLike2<f64>>().unwrap();
 
-        assert!(matches!(extracted_array.0, ArrayLike::PyRef(_)));
+        assert!(extracted_array.is_zero_copy());
         assert_eq!(
             array![[1_f64, 2_f64], [3_f64, 4_f64]],
             extracted_array.into_owned_array()
@@ -35,7 +48,11 @@ fn convert_array_on_extract() {
         let py_array = eval(py, "np.array([[1,2],[3,4]], dtype='int')");
         let extracted_array = py_array.extract::<PyArrayLike2<f64>>().unwrap();
 
-        assert!(matches!(extracted_array.0, ArrayLike::Owned(_, _)));
+        assert!(!extracted_array.is_zero_copy());
+        assert_eq!(
+            extracted_array.provenance(),
+            ArrayLikeKind::CopiedFromNdArray
+        );
         assert_eq!(
             array![[1_f64, 2_f64], [3_f64, 4_f64]],
             extracted_array.into_owned_array()
@@ -49,18 +66,48 @@ fn convert_list_on_extract() {
         let py_list = eval(py, "[[1,2],[3,4]]");
         let extracted_array = py_list.extract::<PyArrayLike2<i32>>().unwrap();
 
-        assert!(matches!(extracted_array.0, ArrayLike::Owned(_, _)));
+        assert!(!extracted_array.is_zero_copy());
+        assert_eq!(
+            extracted_array.provenance(),
+            ArrayLikeKind::BuiltFromSequence
+        );
         assert_eq!(array![[1, 2], [3, 4]], extracted_array.into_owned_array());
     });
 }
 
+#[test]
+fn convert_arrays_of_mixed_dtypes_in_list() {
+    Python::with_gil(|py| {
+        let py_list = eval(
+            py,
+            "[np.array([1, 2], dtype='int8'), np.array([3.5, 4], dtype='float64'), [5, 6]]",
+        );
+        let extracted_array = py_list.extract::<PyArrayLike2<f64>>().unwrap();
+        assert_eq!(
+            extracted_array.view(),
+            array![[1.0, 2.0], [3.5, 4.0], [5.0, 6.0]]
+        );
+
+        let extracted_array = eval(py, "[np.array([1, 2], dtype='int64'), [3, 4]]")
+            .extract::<PyArrayLike2<u8>>()
+            .unwrap();
+        assert_eq!(extracted_array.view(), array![[1, 2], [3, 4]]);
+
+        let py_list = eval(py, "[np.array([1.5, 2.5]), [3, 4]]");
+        assert!(py_list.extract::<PyArrayLike2<i32>>().is_err());
+        let options = ExtractOptions::new().unsafe_casting(true);
+        let extracted_array = PyArrayLike2::<i32>::extract_with(&py_list, &options).unwrap();
+        assert_eq!(extracted_array.view(), array![[1, 2], [3, 4]]);
+    });
+}
+
 #[test]
 fn convert_array_in_list_on_extract() {
     Python::with_gil(|py| {
         let py_array = eval(py, "[np.array([1, 2], dtype='int32'), [3, 4]]");
         let extracted_array = py_array.extract::<PyArrayLike2<i32>>().unwrap();
 
-        assert!(matches!(extracted_array.0, ArrayLike::Owned(_, _)));
+        assert!(!extracted_array.is_zero_copy());
         assert_eq!(array![[1, 2], [3, 4]], extracted_array.into_owned_array());
     });
 }
@@ -71,7 +118,7 @@ fn convert_list_on_extract_dyn() {
         let py_list = eval(py, "[[[1,2],[3,4]],[[5,6],[7,8]]]");
         let extracted_array = py_list.extract::<PyArrayLikeDyn<i32>>().unwrap();
 
-        assert!(matches!(extracted_array.0, ArrayLike::Owned(_, _)));
+        assert!(!extracted_array.is_zero_copy());
         assert_eq!(
             array![[[1, 2], [3, 4]], [[5, 6], [7, 8]]].into_dyn(),
             extracted_array.into_owned_array()
@@ -86,8 +133,8 @@ fn convert_1d_list_on_extract() {
         let extracted_array_1d = py_list.extract::<PyArrayLike1<u32>>().unwrap();
         let extracted_array_dyn = py_list.extract::<PyArrayLikeDyn<f64>>().unwrap();
 
-        assert!(matches!(extracted_array_1d.0, ArrayLike::Owned(_, _)));
-        assert!(matches!(extracted_array_dyn.0, ArrayLike::Owned(_, _)));
+        assert!(!extracted_array_1d.is_zero_copy());
+        assert!(!extracted_array_dyn.is_zero_copy());
         assert_eq!(array![1, 2, 3, 4], extracted_array_1d.into_owned_array());
         assert_eq!(
             array![1_f64, 2_f64, 3_f64, 4_f64].into_dyn(),
@@ -116,9 +163,11 @@ fn extract_0d_array() {
         let extraction2 = num.extract::<PyArrayLike0<i32>>().unwrap();
         let extraction3 = num.extract::<PyArrayLikeDyn<usize>>().unwrap();
 
-        assert!(matches!(extraction1.0, ArrayLike::Owned(_, _)));
-        assert!(matches!(extraction2.0, ArrayLike::Owned(_, _)));
-        assert!(matches!(extraction3.0, ArrayLike::Owned(_, _)));
+        assert!(!extraction1.is_zero_copy());
+        assert!(!extraction2.is_zero_copy());
+        assert!(!extraction3.is_zero_copy());
+        assert_eq!(extraction1.provenance(), ArrayLikeKind::CopiedFromNdArray);
+        assert_eq!(extraction2.provenance(), ArrayLikeKind::Scalar);
 
         assert_eq!(extraction1.into_owned_array(), Array0::from_elem((), 1));
         assert_eq!(extraction2.into_owned_array(), Array0::from_elem((), 42));
@@ -128,3 +177,2774 @@ fn extract_0d_array() {
         );
     });
 }
+
+#[test]
+fn matmul_into_pyarray_computes_product() {
+    Python::with_gil(|py| {
+        let a = eval(py, "np.array([[1,2,3],[4,5,6]], dtype='float64')")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        let b = eval(py, "[[1,0],[0,1],[1,1]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+
+        let product = matmul_into_pyarray(py, &a, &b).unwrap();
+
+        assert_eq!(
+            product.to_owned_array(),
+            array![[4_f64, 5_f64], [10_f64, 11_f64]]
+        );
+    });
+}
+
+#[test]
+fn extract_batch_into_pyarray_stack_adds_leading_axis() {
+    Python::with_gil(|py| {
+        let obs = eval(
+            py,
+            "[np.zeros((2, 2)), [[1.0, 1.0], [1.0, 1.0]], np.full((2, 2), 2.0)]",
+        );
+        let stacked = extract_batch_into_pyarray_stack::<f64, Ix2>(py, &obs).unwrap();
+        assert_eq!(
+            stacked.to_owned_array(),
+            ndarray::Array3::from_shape_fn((3, 2, 2), |(i, _, _)| i as f64)
+        );
+
+        let obs = eval(py, "[np.zeros((2, 2)), np.zeros((2, 3))]");
+        let err = extract_batch_into_pyarray_stack::<f64, Ix2>(py, &obs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected all arrays to have shape [2, 2], got shape [2, 3] at index 1."));
+    });
+}
+
+#[test]
+fn stack_and_concatenate_array_likes() {
+    Python::with_gil(|py| {
+        let arrays = ["[1.0, 2.0]", "np.array([3.0, 4.0])", "(5.0, 6.0)"]
+            .map(|code| eval(py, code).extract::<PyArrayLike1<f64>>().unwrap());
+
+        let stacked = PyArrayLike::stack(Axis(0), &arrays).unwrap();
+        assert_eq!(stacked.view(), array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+        let stacked = PyArrayLike::stack(Axis(1), &arrays).unwrap();
+        assert_eq!(stacked.view(), array![[1.0, 3.0, 5.0], [2.0, 4.0, 6.0]]);
+        let joined = PyArrayLike::concatenate(Axis(0), &arrays).unwrap();
+        assert_eq!(joined.view(), array![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let arrays = ["[[1.0, 2.0]]", "[[3.0], [4.0]]"]
+            .map(|code| eval(py, code).extract::<PyArrayLike2<f64>>().unwrap());
+        let err = PyArrayLike::stack(Axis(0), &arrays).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected all arrays to have shape [1, 2], got shape [2, 1] at index 1."));
+        let err = PyArrayLike::concatenate(Axis(0), &arrays).unwrap_err();
+        assert!(err.to_string().contains(
+            "Expected all arrays to match shape [1, 2] except along axis 0, got shape [2, 1] at index 1."
+        ));
+        let err = PyArrayLike::concatenate(Axis(2), &arrays).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Axis 2 is out of bounds for a result of dimension 2."));
+    });
+}
+
+#[test]
+fn interp_linear_function() {
+    Python::with_gil(|py| {
+        let x = eval(py, "[-1.0, 0.5, 1.5, 2.0, 5.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let xp = eval(py, "[0.0, 1.0, 2.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let fp = eval(py, "np.array([0.0, 2.0, 4.0])")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(
+            interp(&x, &xp, &fp).unwrap(),
+            array![0.0, 1.0, 3.0, 4.0, 4.0]
+        );
+
+        let short = eval(py, "[0.0, 1.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let err = interp(&x, &xp, &short).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected xp and fp of equal length, got 3 and 2."));
+
+        let descending = eval(py, "[2.0, 1.0, 0.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let err = interp(&x, &descending, &fp).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("the value at index 1 does not exceed its predecessor"));
+
+        let x = eval(py, "[float('nan'), 0.5]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let single = eval(py, "[1.0]").extract::<PyArrayLike1<f64>>().unwrap();
+        let result = interp(&x, &single, &single).unwrap();
+        assert!(result[0].is_nan());
+        assert_eq!(result[1], 1.0);
+        assert!(interp(&x, &xp, &fp).unwrap()[0].is_nan());
+    });
+}
+
+#[test]
+fn matmul_into_pyarray_rejects_dimension_mismatch() {
+    Python::with_gil(|py| {
+        let a = eval(py, "[[1,2,3],[4,5,6]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        let b = eval(py, "[[1,0],[0,1]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+
+        assert!(matmul_into_pyarray(py, &a, &b).is_err());
+    });
+}
+
+#[test]
+fn gpu_array_shall_fail_with_hint() {
+    Python::with_gil(|py| {
+        let gpu_array = eval(
+            py,
+            "type('CudaArray', (), {'__cuda_array_interface__': {}, '__iter__': lambda self: iter([1, 2])})()",
+        );
+        let err = gpu_array.extract::<PyArrayLike1<f64>>().unwrap_err();
+
+        assert!(err.to_string().contains("GPU arrays are not supported"));
+    });
+}
+
+#[test]
+fn extract_object_array() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1, 'a', 2, 'b'], dtype=object)");
+        let extracted_array = py_array.extract::<PyArrayLike1<Py<PyAny>>>().unwrap();
+
+        assert!(extracted_array.is_zero_copy());
+        let view = extracted_array.view();
+        assert_eq!(view[0].extract::<i64>(py).unwrap(), 1);
+        assert_eq!(view[1].extract::<String>(py).unwrap(), "a");
+        assert_eq!(view[2].extract::<i64>(py).unwrap(), 2);
+        assert_eq!(view[3].extract::<String>(py).unwrap(), "b");
+    });
+}
+
+#[test]
+fn convert_nested_list_of_objects_on_extract() {
+    Python::with_gil(|py| {
+        let py_list = eval(py, "[[{'a': 1}, {}], [{}, {'b': 2}]]");
+        let extracted_2d = py_list.extract::<PyArrayLike2<Py<PyAny>>>().unwrap();
+        let extracted_dyn = py_list.extract::<PyArrayLikeDyn<Py<PyAny>>>().unwrap();
+
+        assert!(!extracted_2d.is_zero_copy());
+        assert_eq!(extracted_2d.view().dim(), (2, 2));
+        assert_eq!(extracted_dyn.view().shape(), &[2, 2]);
+        for element in extracted_dyn.view().iter() {
+            assert!(element.bind(py).is_instance_of::<PyDict>());
+        }
+    });
+}
+
+#[test]
+fn object_extraction_keeps_reference_counts_balanced() {
+    Python::with_gil(|py| {
+        let obj = eval(py, "object()");
+        let py_list = PyList::new_bound(py, [&obj, &obj]);
+        let refcnt = obj.get_refcnt();
+
+        let extracted_array = py_list.extract::<PyArrayLike1<Py<PyAny>>>().unwrap();
+        assert_eq!(obj.get_refcnt(), refcnt + 2);
+        drop(extracted_array);
+        assert_eq!(obj.get_refcnt(), refcnt);
+
+        let owned_array = py_list
+            .extract::<PyArrayLike1<Py<PyAny>>>()
+            .unwrap()
+            .into_owned_array();
+        assert_eq!(obj.get_refcnt(), refcnt + 2);
+        drop(owned_array);
+        assert_eq!(obj.get_refcnt(), refcnt);
+    });
+}
+
+#[cfg(feature = "dlpack")]
+#[test]
+fn extract_dlpack_tensor() {
+    Python::with_gil(|py| {
+        let tensor = eval(
+            py,
+            "type('Tensor', (), {'data': np.arange(4, dtype='float64'), '__dlpack__': lambda self, **kwargs: self.data.__dlpack__(**kwargs), '__dlpack_device__': lambda self: self.data.__dlpack_device__()})()",
+        );
+        let extracted_array = tensor.extract::<PyArrayLike1<f64>>().unwrap();
+
+        assert!(extracted_array.is_zero_copy());
+        assert_eq!(
+            array![0_f64, 1_f64, 2_f64, 3_f64],
+            extracted_array.into_owned_array()
+        );
+    });
+}
+
+#[test]
+fn convert_none_to_nan_on_extract() {
+    Python::with_gil(|py| {
+        let options = ExtractOptions::new().none_as_nan(true);
+
+        let py_list = eval(py, "[1.5, None, 3.0]");
+        let extracted_1d = PyArrayLike1::<f64>::extract_with(&py_list, &options)
+            .unwrap()
+            .into_owned_array();
+        assert_eq!(extracted_1d[0], 1.5);
+        assert!(extracted_1d[1].is_nan());
+        assert_eq!(extracted_1d[2], 3.0);
+
+        let py_list = eval(py, "[[1.0, None], [None, 4.0]]");
+        let extracted_2d = PyArrayLike2::<f64>::extract_with(&py_list, &options)
+            .unwrap()
+            .into_owned_array();
+        assert_eq!(extracted_2d[[0, 0]], 1.0);
+        assert!(extracted_2d[[0, 1]].is_nan());
+        assert!(extracted_2d[[1, 0]].is_nan());
+        assert_eq!(extracted_2d[[1, 1]], 4.0);
+
+        let py_array = eval(py, "np.array([1.0, None, 3.0], dtype=object)");
+        let extracted_object = PyArrayLike1::<f32>::extract_with(&py_array, &options)
+            .unwrap()
+            .into_owned_array();
+        assert_eq!(extracted_object[0], 1.0);
+        assert!(extracted_object[1].is_nan());
+        assert_eq!(extracted_object[2], 3.0);
+    });
+}
+
+#[test]
+fn none_shall_fail_with_index() {
+    Python::with_gil(|py| {
+        let err = eval(py, "[1.5, None, 3.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap_err();
+        assert!(err.to_string().contains("Found None at index [1]."));
+
+        let err = eval(py, "[[1.0, 2.0], [3.0, None]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap_err();
+        assert!(err.to_string().contains("Found None at index [1, 1]."));
+
+        let err = eval(py, "np.array([1.0, None], dtype=object)")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap_err();
+        assert!(err.to_string().contains("Found None at index [1]."));
+    });
+}
+
+#[test]
+fn none_shall_fail_for_integers() {
+    Python::with_gil(|py| {
+        let options = ExtractOptions::new().none_as_nan(true);
+        let py_list = eval(py, "[1, None, 3]");
+
+        assert!(PyArrayLike1::<i64>::extract_with(&py_list, &options).is_err());
+    });
+}
+
+#[test]
+fn source_writeable_reports_flag() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1, 2, 3], dtype='float64')");
+        let writeable = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        assert_eq!(writeable.source_writeable(), Some(true));
+        drop(writeable);
+
+        py_array
+            .getattr("flags")
+            .unwrap()
+            .setattr("writeable", false)
+            .unwrap();
+        let readonly = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        assert_eq!(readonly.source_writeable(), Some(false));
+
+        let owned = eval(py, "[1.0, 2.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(owned.source_writeable(), None);
+    });
+}
+
+#[test]
+fn into_chunk_pyarrays_splits_along_axis() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[1, 2, 3, 4, 5]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        let chunks = extracted_array.into_chunk_pyarrays(Axis(0), 2);
+
+        assert_eq!(chunks.len(), 3);
+        let chunks = chunks
+            .iter()
+            .map(|chunk| chunk.downcast::<PyArray1<i64>>().unwrap().to_owned_array())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, vec![array![1, 2], array![3, 4], array![5]]);
+    });
+}
+
+#[test]
+fn contiguous_extraction_provides_slices() {
+    Python::with_gil(|py| {
+        let c_array = eval(py, "np.array([[1,2,3],[4,5,6]], dtype='float64')");
+        let f_array = eval(
+            py,
+            "np.asfortranarray(np.array([[1,2,3],[4,5,6]], dtype='float64'))",
+        );
+        let c_order = [1_f64, 2_f64, 3_f64, 4_f64, 5_f64, 6_f64];
+        let f_order = [1_f64, 4_f64, 2_f64, 5_f64, 3_f64, 6_f64];
+
+        let extracted = c_array.extract::<PyArrayLikeC<f64, Ix2>>().unwrap();
+        assert!(extracted.is_zero_copy());
+        assert_eq!(extracted.as_slice(), c_order);
+
+        let extracted = f_array.extract::<PyArrayLikeC<f64, Ix2>>().unwrap();
+        assert!(!extracted.is_zero_copy());
+        assert_eq!(extracted.as_slice(), c_order);
+        assert_eq!(extracted.as_slice(), (*extracted).as_slice().unwrap());
+
+        let extracted = f_array.extract::<PyArrayLikeF<f64, Ix2>>().unwrap();
+        assert!(extracted.is_zero_copy());
+        assert_eq!(extracted.as_slice(), f_order);
+
+        let extracted = c_array.extract::<PyArrayLikeF<f64, Ix2>>().unwrap();
+        assert!(!extracted.is_zero_copy());
+        assert_eq!(extracted.as_slice(), f_order);
+        assert_eq!(extracted.view(), array![[1., 2., 3.], [4., 5., 6.]]);
+
+        let extracted = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLikeF<f64, Ix2>>()
+            .unwrap();
+        assert_eq!(extracted.as_slice(), [1_f64, 3_f64, 2_f64, 4_f64]);
+    });
+}
+
+fn assert_matches_numpy(py: Python<'_>, code: &str) {
+    let extracted_array = eval(py, code).extract::<PyArrayLikeDyn<f64>>().unwrap();
+    let expected = eval(py, &format!("np.array({}, dtype='float64')", code));
+    let expected = expected.downcast::<PyArrayDyn<f64>>().unwrap();
+
+    assert!(!extracted_array.is_zero_copy());
+    assert_eq!(extracted_array.view(), expected.readonly().as_array());
+}
+
+#[test]
+fn convert_nested_sequences_like_numpy() {
+    Python::with_gil(|py| {
+        assert_matches_numpy(py, "[1, 2, 3]");
+        assert_matches_numpy(py, "[[1, 2], [3, 4]]");
+        assert_matches_numpy(py, "[(1, 2), (3, 4), (5, 6)]");
+        assert_matches_numpy(py, "[[[1, 2], [3, 4]], [[5, 6], [7, 8]]]");
+        assert_matches_numpy(py, "[np.array([1, 2], dtype='float64'), [3, 4]]");
+        assert_matches_numpy(py, "[np.array([[1, 2]], dtype='float64'), [[3, 4]]]");
+        assert_matches_numpy(py, "[np.array([1, 2], dtype='int8'), (3.5, 4)]");
+        assert_matches_numpy(py, "[[], []]");
+
+        let extracted_array = eval(py, "(iter([i, i + 1]) for i in range(3))")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        assert_eq!(
+            extracted_array.into_owned_array(),
+            array![[0, 1], [1, 2], [2, 3]]
+        );
+    });
+}
+
+#[test]
+fn ragged_sequences_shall_fail_with_index() {
+    Python::with_gil(|py| {
+        let err = eval(py, "[[1, 2], [3, 4, 5]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 2 at index [1], got length 3."));
+
+        let err = eval(py, "[[1, 2], np.array([3, 4, 5], dtype='int32')]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 2 at index [1], got length 3."));
+
+        let err = eval(py, "[[[1], [2]], [[3]]]")
+            .extract::<PyArrayLikeDyn<i32>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 2 at index [1], got length 1."));
+    });
+}
+
+#[test]
+fn ragged_generator_rows_shall_fail_with_index() {
+    Python::with_gil(|py| {
+        let err = eval(py, "(row for row in [[1, 2], [3, 4], [5, 6, 7]])")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 2 at index [2], got length 3."));
+
+        let err = eval(py, "(iter(row) for row in [[1, 2, 3], [4, 5]])")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 3 at index [1], got length 2."));
+    });
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_round_trip() {
+    Python::with_gil(|py| {
+        let borrowed = eval(py, "np.array([[1,2,3],[4,5,6]], dtype='int32')")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        let owned = eval(py, "[[1,2,3],[4,5,6]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+
+        let borrowed_json = serde_json::to_string(&borrowed).unwrap();
+        let owned_json = serde_json::to_string(&owned).unwrap();
+        assert_eq!(borrowed_json, owned_json);
+
+        let deserialized = serde_json::from_str::<ndarray::Array2<i32>>(&borrowed_json).unwrap();
+        assert_eq!(deserialized, owned.into_owned_array());
+    });
+}
+
+#[test]
+fn extract_time_series_validates_input() {
+    Python::with_gil(|py| {
+        let (timestamps, values) = extract_time_series::<i64, f64>(
+            &eval(py, "np.array([1, 2, 5], dtype='int64')"),
+            &eval(py, "[0.5, 1.5, 2.5]"),
+        )
+        .unwrap();
+        assert_eq!(timestamps.into_owned_array(), array![1, 2, 5]);
+        assert_eq!(values.into_owned_array(), array![0.5, 1.5, 2.5]);
+
+        let err = extract_time_series::<i64, f64>(&eval(py, "[1, 2, 5]"), &eval(py, "[0.5, 1.5]"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected timestamps and values of equal length, got 3 and 2."));
+
+        let err = extract_time_series::<f64, f64>(
+            &eval(py, "[1.0, 3.0, 2.0]"),
+            &eval(py, "[0.5, 1.5, 2.5]"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("timestamp at index 2"));
+    });
+}
+
+#[test]
+fn convert_sequences_with_length_hints() {
+    Python::with_gil(|py| {
+        let sized = eval(py, "[[1, 2], [3, 4], [5, 6]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        assert_eq!(sized.into_owned_array(), array![[1, 2], [3, 4], [5, 6]]);
+
+        let hinted = eval(py, "iter([[1, 2], [3, 4], [5, 6]])")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        assert_eq!(hinted.into_owned_array(), array![[1, 2], [3, 4], [5, 6]]);
+
+        let unhinted = eval(py, "(i for i in range(5))")
+            .extract::<PyArrayLike1<i32>>()
+            .unwrap();
+        assert_eq!(unhinted.into_owned_array(), array![0, 1, 2, 3, 4]);
+
+        for len in ["1", "100", "2**33", "2**62"] {
+            let lying = eval(
+                py,
+                &format!(
+                    "type('Lying', (), {{'__len__': lambda self: {}, '__iter__': lambda self: iter([1, 2, 3])}})()",
+                    len
+                ),
+            );
+            let extracted_array = lying.extract::<PyArrayLike1<i32>>().unwrap();
+            assert_eq!(extracted_array.into_owned_array(), array![1, 2, 3]);
+        }
+    });
+}
+
+#[test]
+fn dtype_kind_reports_category() {
+    Python::with_gil(|py| {
+        let float_array = eval(py, "np.array([1.5, 2.5], dtype='float32')")
+            .extract::<PyArrayLike1<f32>>()
+            .unwrap();
+        let int_array = eval(py, "[1, 2]").extract::<PyArrayLike1<i64>>().unwrap();
+        let uint_array = eval(py, "[1, 2]").extract::<PyArrayLike1<u8>>().unwrap();
+
+        assert_eq!(float_array.dtype_kind(), 'f');
+        assert_eq!(int_array.dtype_kind(), 'i');
+        assert_eq!(uint_array.dtype_kind(), 'u');
+    });
+}
+
+#[test]
+fn construct_owned_without_python_object() {
+    Python::with_gil(|py| {
+        let from_owned = PyArrayLike2::from_owned(array![[1, 2], [3, 4]], py);
+        assert!(!from_owned.is_zero_copy());
+        assert_eq!(from_owned.view(), array![[1, 2], [3, 4]]);
+
+        let from_vec = PyArrayLike1::from_vec(vec![1.5, 2.5], py);
+        assert_eq!(from_vec.view(), array![1.5, 2.5]);
+
+        let from_elem = PyArrayLike2::from_elem((2, 3), 7_u8, py);
+        assert_eq!(from_elem.view(), array![[7, 7, 7], [7, 7, 7]]);
+    });
+}
+
+#[test]
+fn asarray_fallback_rescues_array_struct() {
+    Python::with_gil(|py| {
+        let array_struct = eval(
+            py,
+            "type('ArrayStruct', (), {'__array_struct__': np.arange(3, dtype='float64').__array_struct__})()",
+        );
+        let options = ExtractOptions::new().asarray_fallback(true);
+
+        assert!(array_struct.extract::<PyArrayLike1<f64>>().is_err());
+        let extracted_array = PyArrayLike1::<f64>::extract_with(&array_struct, &options).unwrap();
+        assert_eq!(extracted_array.view(), array![0., 1., 2.]);
+    });
+}
+
+#[test]
+fn asarray_fallback_surfaces_numpy_errors() {
+    Python::with_gil(|py| {
+        let failing = eval(
+            py,
+            "type('Failing', (), {'__array__': lambda self, *args, **kwargs: 1 / 0})()",
+        );
+        let options = ExtractOptions::new().asarray_fallback(true);
+
+        let err = PyArrayLike1::<f64>::extract_with(&failing, &options).unwrap_err();
+        assert!(err
+            .cause(py)
+            .unwrap()
+            .is_instance_of::<PyZeroDivisionError>(py));
+
+        let unsafe_cast = eval(
+            py,
+            "type('Floats', (), {'__array__': lambda self, *args, **kwargs: np.array([1.5])})()",
+        );
+        let err = PyArrayLike1::<i32>::extract_with(&unsafe_cast, &options).unwrap_err();
+        assert!(err.cause(py).unwrap().is_instance_of::<PyTypeError>(py));
+    });
+}
+
+#[test]
+fn convert_nested_list_to_fortran_order() {
+    Python::with_gil(|py| {
+        let options = ExtractOptions::new().contiguous(MemoryOrder::F);
+
+        let extracted_array =
+            PyArrayLike2::<f64>::extract_with(&eval(py, "[[1, 2], [3, 4]]"), &options).unwrap();
+        assert!(extracted_array.is_f_contiguous());
+        assert_eq!(extracted_array.view(), array![[1., 2.], [3., 4.]]);
+
+        let extracted_array = PyArrayLike3::<i32>::extract_with(
+            &eval(
+                py,
+                "[[[1, 2, 3], np.array([4, 5, 6], dtype='int32')], ((7, 8, 9), [10, 11, 12])]",
+            ),
+            &options,
+        )
+        .unwrap();
+        assert!(extracted_array.is_f_contiguous());
+        assert_eq!(
+            extracted_array.view(),
+            array![[[1, 2, 3], [4, 5, 6]], [[7, 8, 9], [10, 11, 12]]]
+        );
+
+        let err = PyArrayLike2::<f64>::extract_with(&eval(py, "[[1, 2], [3, 4, 5]]"), &options)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 2 at index [1], got length 3."));
+    });
+}
+
+#[test]
+fn convert_tuple_on_extract() {
+    Python::with_gil(|py| {
+        let py_tuple = eval(py, "(1, 2, 3, 4)");
+        let extracted_array = py_tuple.extract::<PyArrayLike1<i32>>().unwrap();
+
+        assert!(!extracted_array.is_zero_copy());
+        assert_eq!(array![1, 2, 3, 4], extracted_array.into_owned_array());
+    });
+}
+
+#[test]
+fn convert_nested_tuple_on_extract() {
+    Python::with_gil(|py| {
+        let py_tuple = eval(py, "((1, 2), (3, 4))");
+        let extracted_array = py_tuple.extract::<PyArrayLike2<i32>>().unwrap();
+
+        assert!(!extracted_array.is_zero_copy());
+        assert_eq!(array![[1, 2], [3, 4]], extracted_array.into_owned_array());
+    });
+}
+
+#[test]
+fn unsafe_cast_marker_truncates() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1.9, -2.7], dtype='float64')");
+        let py_list = eval(py, "[1.9, -2.7]");
+        let overflowing = eval(py, "np.array([300, 1], dtype='int64')");
+
+        let extracted_array = py_array.extract::<PyArrayLikeUnsafe<i32, Ix1>>().unwrap();
+        assert_eq!(extracted_array.view(), array![1, -2]);
+        let extracted_list = py_list.extract::<PyArrayLikeUnsafe<i32, Ix1>>().unwrap();
+        assert_eq!(extracted_list.view(), array![1, -2]);
+        let extracted_overflow = overflowing.extract::<PyArrayLikeUnsafe<u8, Ix1>>().unwrap();
+        assert_eq!(extracted_overflow.view(), array![44, 1]);
+
+        assert!(py_array.extract::<PyArrayLike1<i32>>().is_err());
+        assert!(py_list.extract::<PyArrayLike1<i32>>().is_err());
+        assert!(py_array
+            .extract::<PyArrayLikeCast<i32, Ix1, CastSafe>>()
+            .is_err());
+    });
+}
+
+#[test]
+fn replace_nan_in_float_array() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.array([1.0, np.nan, 3.0, np.nan])")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+
+        assert_eq!(extracted_array.replace_nan(0.0), array![1.0, 0.0, 3.0, 0.0]);
+        assert_eq!(
+            extracted_array.into_owned_array_nan_to(0.0),
+            array![1.0, 0.0, 3.0, 0.0]
+        );
+
+        let extracted_array = eval(py, "[1.0, float('nan'), 3.0]")
+            .extract::<PyArrayLike1<f32>>()
+            .unwrap();
+        assert_eq!(
+            extracted_array.into_owned_array_nan_to(-1.0),
+            array![1.0, -1.0, 3.0]
+        );
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_copy_matches_serial_copy() {
+    fn check<
+        T: numpy::Element + for<'py> pyo3::FromPyObject<'py> + PartialEq + std::fmt::Debug + 'static,
+    >(
+        py: Python<'_>,
+        code: &str,
+    ) {
+        let ob = eval(py, code);
+        for order in [MemoryOrder::C, MemoryOrder::F] {
+            let serial = ExtractOptions::new()
+                .contiguous(order)
+                .parallel_threshold(usize::MAX);
+            let parallel = ExtractOptions::new()
+                .contiguous(order)
+                .parallel_threshold(0);
+            let serial = PyArrayLikeDyn::<T>::extract_with(&ob, &serial).unwrap();
+            let parallel = PyArrayLikeDyn::<T>::extract_with(&ob, &parallel).unwrap();
+            assert_eq!(parallel.view(), serial.view());
+            assert_eq!(parallel.as_slice().is_some(), order == MemoryOrder::C);
+        }
+    }
+
+    Python::with_gil(|py| {
+        check::<f64>(
+            py,
+            "np.arange(24, dtype='float64').reshape(2,3,4).transpose(1,0,2)",
+        );
+        check::<f64>(
+            py,
+            "np.arange(30, dtype='float64').reshape(5,6)[::-2, 1::2]",
+        );
+        check::<i32>(
+            py,
+            "np.arange(12, dtype='int32').reshape(3,4, order='F')[:, ::-1]",
+        );
+        check::<bool>(py, "(np.arange(20) % 3 == 0).reshape(4,5).T");
+        check::<f64>(py, "np.arange(24, dtype='int32').reshape(4,6)");
+        check::<f64>(
+            py,
+            "np.linspace(0, 1, 30, dtype='float32').reshape(5,6)[::-1, ::2]",
+        );
+        check::<i64>(py, "np.arange(-12, 12, dtype='int8').reshape(2,3,4).T");
+        check::<u32>(py, "np.arange(20, dtype='uint16').reshape(4,5, order='F')");
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_cast_matches_serial_cast() {
+    let array =
+        ndarray::Array::from_shape_fn((4, 5, 6), |(i, j, k)| (i * 30 + j * 6 + k) as i32 - 60);
+    let view = array.slice(ndarray::s![..;-1, 1.., ..;2]);
+    let cast = crate::parallel::cast_view::<i32, f64, _>(view).unwrap();
+    assert_eq!(cast, view.mapv(f64::from));
+    let cast = crate::parallel::cast_view::<i32, i64, _>(view).unwrap();
+    assert_eq!(cast, view.mapv(i64::from));
+    assert!(crate::parallel::cast_view::<i32, bool, _>(view).is_none());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_copy_keeps_strided_views_intact() {
+    let array = ndarray::Array::from_shape_fn((3, 4, 5), |(i, j, k)| (i * 100 + j * 10 + k) as i64);
+    let view = array
+        .slice(ndarray::s![..;-1, 1.., ..;2])
+        .permuted_axes([2, 0, 1]);
+    for order in [MemoryOrder::C, MemoryOrder::F] {
+        let parallel = crate::parallel::to_contiguous(view.view(), order, 0);
+        assert_eq!(parallel, view);
+    }
+    assert!(crate::parallel::to_contiguous(view.view(), MemoryOrder::C, 0).is_standard_layout());
+    assert!(
+        crate::parallel::to_contiguous(view.view(), MemoryOrder::F, 0)
+            .t()
+            .is_standard_layout()
+    );
+}
+
+#[test]
+fn contiguous_copies_keep_their_layout() {
+    let array = ndarray::Array::from_shape_fn((30, 40).f(), |(i, j)| (i * 100 + j) as f64);
+    let copy = crate::parallel::to_owned(array.view(), 0);
+    assert_eq!(copy, array);
+    assert!(copy.t().is_standard_layout());
+
+    let array = array.as_standard_layout().into_owned();
+    let copy = crate::parallel::to_contiguous(array.view(), MemoryOrder::C, 0);
+    assert_eq!(copy, array);
+    assert!(copy.is_standard_layout());
+}
+
+#[test]
+fn fill_masked_entries() {
+    Python::with_gil(|py| {
+        let ob = eval(
+            py,
+            "np.ma.array([[1.5, 2.5], [3.5, 4.5]], mask=[[False, True], [True, False]])",
+        );
+        let err = ob.extract::<PyArrayLike2<f64>>().unwrap_err();
+        assert!(err.to_string().contains("Masked arrays are not supported"));
+
+        let masked = ob.extract::<PyArrayLikeMasked<f64, Ix2>>().unwrap();
+        assert_eq!(masked.mask().unwrap(), array![[false, true], [true, false]]);
+        assert_eq!(masked.fill(0.0).view(), array![[1.5, 0.0], [0.0, 4.5]]);
+
+        let unmasked = eval(py, "np.ma.array([1, 2, 3], dtype='int32')")
+            .extract::<PyArrayLikeMasked<i32, Ix1>>()
+            .unwrap();
+        assert!(unmasked.mask().is_none());
+        assert!(unmasked.fill(0).is_zero_copy());
+
+        let nested = eval(
+            py,
+            "[np.ma.array([1.5, 2.5], mask=[False, True]), np.array([3.5, 4.5])]",
+        );
+        let err = nested.extract::<PyArrayLike2<f64>>().unwrap_err();
+        assert!(err.to_string().contains("Masked arrays are not supported"));
+        let nested = eval(py, "[np.ma.array([1.5, 2.5]), np.array([3.5, 4.5])]");
+        let extracted_array = nested.extract::<PyArrayLike2<f64>>().unwrap();
+        assert_eq!(extracted_array.view(), array![[1.5, 2.5], [3.5, 4.5]]);
+    });
+}
+
+#[test]
+fn reductions_over_integer_array() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[[3, -1, 4], [1, 5, 9]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+
+        assert_eq!(extracted_array.sum(), 21);
+        assert_eq!(extracted_array.mean(), Some(3));
+        assert_eq!(extracted_array.min(), Some(-1));
+        assert_eq!(extracted_array.max(), Some(9));
+
+        let empty = eval(py, "np.zeros((0, 3), dtype='int64')")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert_eq!(empty.sum(), 0);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.max(), None);
+
+        for code in [
+            "[np.nan, 1.0, 2.0]",
+            "[1.0, np.nan, 2.0]",
+            "[1.0, 2.0, np.nan]",
+        ] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike1<f64>>().unwrap();
+            assert!(extracted_array.min().unwrap().is_nan(), "{}", code);
+            assert!(extracted_array.max().unwrap().is_nan(), "{}", code);
+        }
+    });
+}
+
+#[test]
+fn clamp_and_abs_elementwise() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.array([[-5, 0], [3, 12]], dtype='int32')")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        let clamped = extracted_array.clamp(-2, 10);
+        assert_eq!(clamped.view(), array![[-2, 0], [3, 10]]);
+        assert_eq!(extracted_array.view(), array![[-5, 0], [3, 12]]);
+
+        let extracted_array = eval(py, "[-1.5, -0.0, 2.5]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.abs().view(), array![1.5, 0.0, 2.5]);
+    });
+}
+
+#[test]
+fn extract_weights_validates_and_normalizes() {
+    Python::with_gil(|py| {
+        let weights = extract_weights::<f64>(&eval(py, "[1.0, 3.0, 0.0, 4.0]"), true).unwrap();
+        assert_eq!(weights.view(), array![0.125, 0.375, 0.0, 0.5]);
+
+        let weights = extract_weights::<f64>(&eval(py, "np.array([2.0, 6.0])"), false).unwrap();
+        assert!(weights.is_zero_copy());
+
+        let err = extract_weights::<f64>(&eval(py, "[0.0, 0.0, 0.0]"), true).unwrap_err();
+        assert!(err.to_string().contains("all weights are zero"));
+
+        let err = extract_weights::<f64>(&eval(py, "[0.5, -0.5]"), false).unwrap_err();
+        assert!(err.to_string().contains("weight at index 1"));
+    });
+}
+
+#[test]
+fn into_owned_array_allow_threads_lets_python_run() {
+    // Counted on the Rust side, so that reading the count does not run Python code, which could switch threads.
+    static SPINS: AtomicU64 = AtomicU64::new(0);
+
+    Python::with_gil(|py| {
+        let spin = PyCFunction::new_closure_bound(py, None, None, |_, _| {
+            SPINS.fetch_add(1, Ordering::Relaxed);
+        })
+        .unwrap();
+        let globals = [
+            ("np", get_array_module(py).unwrap().into_any()),
+            ("spin_once", spin.into_any()),
+        ]
+        .into_py_dict_bound(py);
+        py.run_bound(
+            r#"
+import threading
+state = {"stop": False}
+started = threading.Barrier(2)
+def spin():
+    started.wait()
+    while not state["stop"]:
+        spin_once()
+thread = threading.Thread(target=spin)
+thread.start()
+array = np.arange(4_000_000, dtype='float64').reshape(2000, 2000).T
+# Ensures the thread is running and waiting for the GIL before the copies start.
+started.wait()
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let array = py.eval_bound("array", Some(&globals), None).unwrap();
+        let extracted_array = array.extract::<PyArrayLike2<f64>>().unwrap();
+        let control_array = array.extract::<PyArrayLike2<f64>>().unwrap();
+
+        let before = SPINS.load(Ordering::Relaxed);
+        let expected = control_array.into_owned_array();
+        let after_control = SPINS.load(Ordering::Relaxed);
+        let owned_array = extracted_array.into_owned_array_allow_threads();
+        let after = SPINS.load(Ordering::Relaxed);
+
+        py.run_bound("state['stop'] = True\nthread.join()", Some(&globals), None)
+            .unwrap();
+        assert_eq!(owned_array, expected);
+        assert_eq!(after_control, before);
+        assert!(after > after_control);
+    });
+}
+
+#[test]
+fn as_2d_view_borrows_matching_dimension() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([[1.0, 2.0], [3.0, 4.0]])");
+        let extracted_array = py_array.extract::<PyArrayLikeDyn<f64>>().unwrap();
+        let view = extracted_array.as_2d_view().unwrap();
+        assert_eq!(view, array![[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(
+            view.as_ptr(),
+            py_array.downcast::<PyArrayDyn<f64>>().unwrap().data() as *const f64
+        );
+
+        let extracted_array = eval(py, "[1.0, 2.0]")
+            .extract::<PyArrayLikeDyn<f64>>()
+            .unwrap();
+        let err = extracted_array.as_2d_view().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected an array of dimension 2, got dimension 1."));
+    });
+}
+
+#[test]
+fn convert_list_of_contiguous_arrays_on_extract() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(
+            py,
+            "[np.arange(1024, dtype='float32') + i for i in range(64)]",
+        )
+        .extract::<PyArrayLike2<f32>>()
+        .unwrap();
+        let expected = ndarray::Array2::from_shape_fn((64, 1024), |(i, j)| (i + j) as f32);
+        assert_eq!(extracted_array.view(), expected);
+
+        let extracted_array = eval(
+            py,
+            "[np.arange(6, dtype='float32')[::2], [6.0, 7.0, 8.0], np.array([9, 10, 11], dtype='float32')]",
+        )
+        .extract::<PyArrayLike2<f32>>()
+        .unwrap();
+        assert_eq!(
+            extracted_array.view(),
+            array![[0.0, 2.0, 4.0], [6.0, 7.0, 8.0], [9.0, 10.0, 11.0]]
+        );
+
+        let err = eval(
+            py,
+            "[np.zeros(4, dtype='float32'), np.zeros(4, dtype='float32'), np.zeros(5, dtype='float32')]",
+        )
+        .extract::<PyArrayLike2<f32>>()
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 4 at index [2], got length 5."));
+    });
+}
+
+#[test]
+fn copy_into_reuses_buffer() {
+    Python::with_gil(|py| {
+        let mut out = ndarray::Array2::<i32>::zeros((2, 2));
+        let ptr = out.as_ptr();
+
+        eval(py, "np.array([[1, 2], [3, 4]], dtype='int32')")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap()
+            .copy_into(&mut out)
+            .unwrap();
+        assert_eq!(out, array![[1, 2], [3, 4]]);
+
+        eval(py, "[[5, 6], [7, 8]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap()
+            .copy_into(&mut out)
+            .unwrap();
+        assert_eq!(out, array![[5, 6], [7, 8]]);
+        assert_eq!(out.as_ptr(), ptr);
+
+        let err = eval(py, "[[1, 2, 3]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap()
+            .copy_into(&mut out)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected an output array of shape [1, 3], got shape [2, 2]."));
+    });
+}
+
+#[test]
+fn convert_empty_sequences_on_extract() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[]").extract::<PyArrayLike1<f64>>().unwrap();
+        assert_eq!(extracted_array.view().shape(), [0]);
+
+        let extracted_array = eval(py, "[]").extract::<PyArrayLikeDyn<f64>>().unwrap();
+        assert_eq!(extracted_array.view().shape(), [0]);
+
+        let extracted_array = eval(py, "[]").extract::<PyArrayLike2<f64>>().unwrap();
+        assert_eq!(extracted_array.view().shape(), [0, 0]);
+
+        let extracted_array = eval(py, "[[], []]").extract::<PyArrayLike3<f64>>().unwrap();
+        assert_eq!(extracted_array.view().shape(), [2, 0, 0]);
+
+        assert_matches_numpy(py, "[[], []]");
+        assert_matches_numpy(py, "[[[]]]");
+
+        let err = eval(py, "[[], [1.0]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a sequence of length 0 at index [1], got length 1."));
+    });
+}
+
+#[test]
+fn dispatch_by_kind_of_object() {
+    Python::with_gil(|py| {
+        // Sequences
+        let extracted_array = eval(
+            py,
+            "[[[float(i + j + k) for k in range(40)] for j in range(40)] for i in range(40)]",
+        )
+        .extract::<PyArrayLikeDyn<f64>>()
+        .unwrap();
+        assert_eq!(extracted_array.view().shape(), [40, 40, 40]);
+        assert_eq!(
+            extracted_array.view().sum(),
+            3. * 40. * 40. * 40. * 39. / 2.
+        );
+
+        let extracted_array = eval(py, "[1, 2]")
+            .extract::<PyArrayLike0<Py<PyAny>>>()
+            .unwrap();
+        assert!(extracted_array.view()[()]
+            .bind(py)
+            .is_instance_of::<PyList>());
+
+        // Numpy arrays
+        assert_matches_numpy(py, "[np.array([1.0, 2.0]), np.array([3.0, 4.0])]");
+
+        // Scalars
+        let extracted_array = eval(py, "5.0").extract::<PyArrayLikeDyn<f64>>().unwrap();
+        assert_eq!(extracted_array.view().shape(), [] as [usize; 0]);
+        assert!(eval(py, "['ab', 'cd']")
+            .extract::<PyArrayLikeDyn<f64>>()
+            .is_err());
+
+        // Other objects
+        assert_matches_numpy(py, "[np.float64(1.0), np.int32(2)]");
+        let extracted_array = eval(py, "(float(i) for i in range(3))")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.view(), array![0.0, 1.0, 2.0]);
+    });
+}
+
+#[test]
+fn cancel_extraction_via_callback() {
+    Python::with_gil(|py| {
+        let py_list = eval(py, "[float(i) for i in range(300_000)]");
+
+        let mut reported = Vec::new();
+        let err = PyArrayLike1::<f64>::from_python_with_callback(&py_list, |count| {
+            reported.push(count);
+            count < 100_000
+        })
+        .unwrap_err();
+        assert!(err.is_instance_of::<PyKeyboardInterrupt>(py));
+        assert_eq!(reported, [65_536, 131_072]);
+
+        let mut reported = Vec::new();
+        let extracted_array = PyArrayLike1::<f64>::from_python_with_callback(&py_list, |count| {
+            reported.push(count);
+            true
+        })
+        .unwrap();
+        assert_eq!(extracted_array.view().len(), 300_000);
+        assert_eq!(reported, [65_536, 131_072, 196_608, 262_144]);
+    });
+}
+
+#[test]
+fn softmax_along_rows() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[[1.0, 2.0, 3.0], [1000.0, 1000.0, 1000.0]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+
+        let softmax = extracted_array.softmax(Axis(1)).unwrap();
+        for row in softmax.rows() {
+            assert!((row.sum() - 1.0).abs() < 1e-12);
+        }
+        assert!(softmax[[0, 0]] < softmax[[0, 1]] && softmax[[0, 1]] < softmax[[0, 2]]);
+        assert!((softmax[[1, 0]] - 1.0 / 3.0).abs() < 1e-12);
+
+        let err = extracted_array.softmax(Axis(2)).unwrap_err();
+        assert!(err.to_string().contains("Axis 2 is out of bounds"));
+        let empty = PyArrayLike2::<f64>::from_owned(ndarray::Array2::zeros((3, 0)), py);
+        assert_eq!(empty.softmax(Axis(1)).unwrap().dim(), (3, 0));
+    });
+}
+
+#[test]
+fn dtype_reports_numpy_descriptor() {
+    Python::with_gil(|py| {
+        let borrowed = eval(py, "np.array([1, 2], dtype='int32')")
+            .extract::<PyArrayLike1<i32>>()
+            .unwrap();
+        let owned = eval(py, "[1.5, 2.5]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+
+        let name = |dtype: Bound<'_, numpy::PyArrayDescr>| {
+            dtype.getattr("name").unwrap().extract::<String>().unwrap()
+        };
+        assert_eq!(name(borrowed.dtype()), "int32");
+        assert_eq!(name(owned.dtype()), "float64");
+
+        let borrowed = eval(py, "np.array([1.5, 2.5], dtype='float32')")
+            .extract::<PyArrayLike1<f32>>()
+            .unwrap();
+        assert_eq!(name(borrowed.dtype()), "float32");
+        let owned = eval(py, "[1, 2]").extract::<PyArrayLike1<f64>>().unwrap();
+        assert_eq!(name(owned.dtype()), "float64");
+    });
+}
+
+#[test]
+fn for_each_chunk_matches_eager_extraction() {
+    Python::with_gil(|py| {
+        for code in [
+            "(float(i) for i in range(1000))",
+            "[float(i) for i in range(100_000)]",
+            "np.arange(1000, dtype='float64')",
+            "np.arange(2000, dtype='float64')[::2]",
+            "memoryview(np.arange(1000, dtype='float64'))",
+        ] {
+            let mut sum = 0.0;
+            let mut chunk_lens = Vec::new();
+            PyArrayLike1::<f64>::for_each_chunk(&eval(py, code), 300, |chunk| {
+                chunk_lens.push(chunk.len());
+                sum += chunk.iter().sum::<f64>();
+                Ok(())
+            })
+            .unwrap();
+
+            let eager = eval(py, code).extract::<PyArrayLike1<f64>>().unwrap();
+            assert_eq!(sum, eager.sum());
+            assert_eq!(chunk_lens.iter().sum::<usize>(), eager.view().len());
+            assert!(chunk_lens[..chunk_lens.len() - 1]
+                .iter()
+                .all(|&len| len == 300));
+        }
+
+        let err = PyArrayLike1::<f64>::for_each_chunk(&eval(py, "[1.0, 2.0, 'x']"), 2, |_| Ok(()))
+            .unwrap_err();
+        assert!(err.to_string().contains("at index 2"));
+
+        let masked = eval(py, "np.ma.masked_array([1.0, 2.0], mask=[False, True])");
+        let err = PyArrayLike1::<f64>::for_each_chunk(&masked, 2, |_| Ok(())).unwrap_err();
+        assert!(err.to_string().contains("Masked arrays are not supported"));
+        let mut total = 0.0;
+        PyArrayLike1::<f64>::for_each_chunk(
+            &eval(py, "np.ma.masked_array([1.0, 2.0])"),
+            2,
+            |chunk| {
+                total += chunk.iter().sum::<f64>();
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(total, 3.0);
+
+        let mut chunks = Vec::new();
+        PyArrayLike1::<u8>::for_each_chunk(&eval(py, "b'abc'"), 2, |chunk| {
+            chunks.push(chunk.to_vec());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(chunks, [b"ab".to_vec(), b"c".to_vec()]);
+
+        let err =
+            PyArrayLike1::<u8>::for_each_chunk(&eval(py, "'abc'"), 2, |_| Ok(())).unwrap_err();
+        assert!(err.to_string().contains("got 'str'"));
+    });
+}
+
+#[test]
+fn extract_adjacency_matrix_validates_entries() {
+    Python::with_gil(|py| {
+        let matrix =
+            extract_adjacency_matrix::<u8>(&eval(py, "[[0, 1, 1], [1, 0, 0], [1, 0, 0]]")).unwrap();
+        assert_eq!(matrix.view(), array![[0, 1, 1], [1, 0, 0], [1, 0, 0]]);
+
+        let err = extract_adjacency_matrix::<u8>(&eval(py, "[[0, 1, 1], [1, 0, 0]]")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a square adjacency matrix, got shape (2, 3)."));
+
+        let err =
+            extract_adjacency_matrix::<i64>(&eval(py, "np.array([[0, 1], [2, 0]])")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("the entry at index (1, 0) is neither"));
+    });
+}
+
+#[test]
+fn extract_probability_simplex_validates_rows() {
+    Python::with_gil(|py| {
+        let matrix = extract_probability_simplex::<f64>(
+            &eval(py, "[[0.2, 0.8], [0.5, 0.5], [1.0, 0.0]]"),
+            1e-9,
+        )
+        .unwrap();
+        assert_eq!(matrix.view(), array![[0.2, 0.8], [0.5, 0.5], [1.0, 0.0]]);
+
+        let err = extract_probability_simplex::<f64>(&eval(py, "[[0.5, 0.5], [0.1, 1.0]]"), 1e-9)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected every row to sum to 1, but row 1 sums to 1.1."));
+
+        let err = extract_probability_simplex::<f64>(&eval(py, "[[1.5, -0.5]]"), 1e-9).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("row 0 contains a negative or NaN entry"));
+    });
+}
+
+#[test]
+fn extract_onehot_decodes_class_indices() {
+    Python::with_gil(|py| {
+        let indices = extract_onehot::<f64>(&eval(
+            py,
+            "[[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]",
+        ))
+        .unwrap();
+        assert_eq!(indices, array![1, 0, 2]);
+
+        let err = extract_onehot::<i64>(&eval(py, "np.array([[0, 1], [1, 1]])")).unwrap_err();
+        assert!(err.to_string().contains("row 1 is not one-hot"));
+
+        let err = extract_onehot::<i64>(&eval(py, "[[0, 0], [0, 1]]")).unwrap_err();
+        assert!(err.to_string().contains("row 0 is not one-hot"));
+    });
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct Point {
+    x: f64,
+    y: i32,
+}
+
+unsafe impl numpy::Element for Point {
+    const IS_COPY: bool = true;
+
+    fn get_dtype_bound(py: Python<'_>) -> Bound<'_, numpy::PyArrayDescr> {
+        eval(py, "np.dtype([('x', 'f8'), ('y', 'i4')], align=True)")
+            .downcast_into()
+            .unwrap()
+    }
+}
+
+impl<'py> pyo3::FromPyObject<'py> for Point {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> pyo3::PyResult<Self> {
+        Ok(Point {
+            x: ob.get_item("x")?.extract()?,
+            y: ob.get_item("y")?.extract()?,
+        })
+    }
+}
+
+#[test]
+fn extract_record_array() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(
+            py,
+            "np.array([(1.5, 2), (3.5, 4)], dtype=np.dtype([('x', 'f8'), ('y', 'i4')], align=True))",
+        )
+        .extract::<PyArrayLike1<Point>>()
+        .unwrap();
+        assert!(extracted_array.is_zero_copy());
+        assert_eq!(
+            extracted_array.view(),
+            array![Point { x: 1.5, y: 2 }, Point { x: 3.5, y: 4 }]
+        );
+
+        assert!(eval(py, "[(1.5, 2), (3.5, 4)]")
+            .extract::<PyArrayLike1<Point>>()
+            .is_err());
+    });
+}
+
+#[pyo3::pyclass]
+struct CachedArray {
+    data: PyArrayLikeOwned<f64, Ix1>,
+}
+
+#[pyo3::pymethods]
+impl CachedArray {
+    fn total(&self, py: Python<'_>) -> f64 {
+        self.data.with_view(py, |view| view.sum())
+    }
+}
+
+#[test]
+fn store_unbound_array_like_in_pyclass() {
+    let (array, cache) = Python::with_gil(|py| {
+        let array = eval(py, "np.array([1.0, 2.0, 3.0])").unbind();
+        let data = array
+            .bind(py)
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap()
+            .unbind();
+        let cache = Py::new(py, CachedArray { data }).unwrap();
+        (array, cache)
+    });
+
+    Python::with_gil(|py| {
+        let total = cache.call_method0(py, "total").unwrap();
+        assert_eq!(total.extract::<f64>(py).unwrap(), 6.0);
+        assert_eq!(array.get_refcnt(py), 2);
+
+        let bound = cache.borrow(py).data.bind(py);
+        assert!(bound.is_zero_copy());
+        drop(bound);
+
+        let options = ExtractOptions::new().unsafe_casting(true);
+        let cast = PyArrayLike1::<i32>::extract_with(&eval(py, "np.array([1.5, 2.5])"), &options)
+            .unwrap()
+            .unbind();
+        assert_eq!(cast.bind(py).provenance(), ArrayLikeKind::CopiedFromNdArray);
+        assert!(!cast.bind(py).is_zero_copy());
+
+        let bound = cache.borrow(py).data.bind(py);
+        let py_array = array.bind(py).downcast::<PyArray1<f64>>().unwrap();
+        assert_eq!(bound.view().as_ptr(), py_array.data() as *const f64);
+    });
+
+    Python::with_gil(|py| {
+        drop(cache);
+        assert_eq!(array.get_refcnt(py), 1);
+
+        let owned = eval(py, "[4.0, 5.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap()
+            .unbind();
+        assert_eq!(owned.with_view(py, |view| view.sum()), 9.0);
+        assert_eq!(
+            owned.bind(py).provenance(),
+            ArrayLikeKind::BuiltFromSequence
+        );
+        assert_eq!(
+            owned.bind(py).view().as_ptr(),
+            owned.bind(py).view().as_ptr()
+        );
+        assert_eq!(owned.into_owned_array(py), array![4.0, 5.0]);
+    });
+}
+
+#[test]
+fn flip_reverses_all_axes() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.array([[1, 2, 3], [4, 5, 6]], dtype='int32')")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+
+        let flipped = extracted_array.flip();
+        assert_eq!(flipped, array![[6, 5, 4], [3, 2, 1]]);
+        assert_eq!(flipped[[0, 0]], extracted_array.view()[[1, 2]]);
+        assert!(flipped.is_standard_layout());
+    });
+}
+
+#[pyo3::pyfunction]
+fn identity<'py>(ar: PyArrayLike2<'py, f64>) -> PyArrayLike2<'py, f64> {
+    ar
+}
+
+#[test]
+fn return_array_like_from_pyfunction() {
+    Python::with_gil(|py| {
+        let locals = PyDict::new_bound(py);
+        locals
+            .set_item("np", get_array_module(py).unwrap())
+            .unwrap();
+        locals
+            .set_item(
+                "identity",
+                pyo3::wrap_pyfunction_bound!(identity, py).unwrap(),
+            )
+            .unwrap();
+        py.run_bound(
+            r#"
+a = np.array([[1.0, 2.0], [3.0, 4.0]])
+assert identity(a) is a
+b = identity([[1.0, 2.0], [3.0, 4.0]])
+assert isinstance(b, np.ndarray) and b.flags.writeable
+assert (a == b).all()
+"#,
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn into_bound_object_returns_original_object() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.arange(6.0).reshape(2, 3)[:, 1:]");
+        let extracted_array = py_array.extract::<PyArrayLike2<f64>>().unwrap();
+        assert!(extracted_array.into_bound_object().is(&py_array));
+
+        let py_list = eval(py, "[[1.0, 2.0], [3.0, 4.0]]");
+        let extracted_array = py_list.extract::<PyArrayLike2<f64>>().unwrap();
+        let object = extracted_array.into_bound_object();
+        assert!(!object.is(&py_list));
+        assert_eq!(
+            object.downcast::<PyArray2<f64>>().unwrap().to_owned_array(),
+            array![[1.0, 2.0], [3.0, 4.0]]
+        );
+    });
+}
+
+#[test]
+fn to_pyarray_keeps_array_like_usable() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1.0, 2.0, 3.0])");
+        let extracted_array = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        assert!(extracted_array.to_pyarray().is(&py_array));
+        assert_eq!(extracted_array.view(), array![1.0, 2.0, 3.0]);
+
+        let extracted_array = eval(py, "[1.0, 2.0, 3.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let first = extracted_array.to_pyarray();
+        let second = extracted_array.to_pyarray();
+        assert!(!first.is(&second));
+        assert_eq!(first.to_owned_array(), extracted_array.view());
+        assert_eq!(second.to_owned_array(), array![1.0, 2.0, 3.0]);
+    });
+}
+
+#[test]
+fn try_view_mut_writes_through_to_numpy() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1.0, 2.0, 3.0])");
+        let mut extracted_array = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        extracted_array.try_view_mut().unwrap()[0] = 10.0;
+        extracted_array.try_view_mut().unwrap()[2] = 30.0;
+        assert_eq!(extracted_array.view(), array![10.0, 2.0, 30.0]);
+        drop(extracted_array);
+        assert_eq!(
+            py_array
+                .downcast::<PyArray1<f64>>()
+                .unwrap()
+                .to_vec()
+                .unwrap(),
+            [10.0, 2.0, 30.0]
+        );
+
+        let mut first = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        let second = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        assert!(first.try_view_mut().is_none());
+        assert!(matches!(first.0, ArrayLike::PyRef(_)));
+        drop(second);
+
+        let mut exclusive = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        exclusive.try_view_mut().unwrap()[1] = 20.0;
+        for _ in 0..2 {
+            assert!(py_array.extract::<PyArrayLike1<f64>>().is_err());
+        }
+        drop(exclusive);
+        assert_eq!(
+            py_array.extract::<PyArrayLike1<f64>>().unwrap().view(),
+            array![10.0, 20.0, 30.0]
+        );
+
+        let readonly = eval(py, "np.array([1.0, 2.0])");
+        readonly
+            .getattr("flags")
+            .unwrap()
+            .setattr("writeable", false)
+            .unwrap();
+        let mut extracted_array = readonly.extract::<PyArrayLike1<f64>>().unwrap();
+        assert!(extracted_array.try_view_mut().is_none());
+
+        let mut owned = eval(py, "[1.0, 2.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert!(owned.try_view_mut().is_none());
+    });
+}
+
+#[test]
+fn convert_str_to_utf8_bytes_on_request() {
+    Python::with_gil(|py| {
+        let options = ExtractOptions::new().str_as_utf8(true);
+        let extracted_array =
+            PyArrayLike1::<u8>::extract_with(&eval(py, "'abc'"), &options).unwrap();
+        assert_eq!(extracted_array.view(), array![97, 98, 99]);
+
+        let extracted_array =
+            PyArrayLikeDyn::<u8>::extract_with(&eval(py, "'é'"), &options).unwrap();
+        assert_eq!(
+            extracted_array.view().into_dimensionality::<Ix1>().unwrap(),
+            array![195, 169]
+        );
+
+        assert!(eval(py, "'abc'").extract::<PyArrayLike1<u8>>().is_err());
+        assert!(PyArrayLike1::<i64>::extract_with(&eval(py, "'abc'"), &options).is_err());
+    });
+}
+
+#[test]
+fn pairwise_euclidean_distances() {
+    Python::with_gil(|py| {
+        for code in [
+            "[[0.0, 0.0], [3.0, 4.0], [6.0, 8.0]]",
+            "np.array([[0.0, 0.0], [3.0, 4.0], [6.0, 8.0]])",
+        ] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike2<f64>>().unwrap();
+            assert_eq!(
+                extracted_array.pairwise_euclidean(),
+                array![[0.0, 5.0, 10.0], [5.0, 0.0, 5.0], [10.0, 5.0, 0.0]]
+            );
+        }
+    });
+}
+
+#[test]
+fn into_vec_returns_row_major_elements() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.arange(12, dtype='int64').reshape(3, 4)[:, ::2]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.into_vec(), [0, 2, 4, 6, 8, 10]);
+
+        let extracted_array = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        let ptr = extracted_array.view().as_ptr();
+        let vec = extracted_array.into_vec();
+        assert_eq!(vec, [1, 2, 3, 4]);
+        assert_eq!(vec.as_ptr(), ptr);
+
+        let mut array = ndarray::Array2::from_shape_vec((3, 2), vec![1, 2, 3, 4, 5, 6]).unwrap();
+        array.slice_collapse(ndarray::s![1..2, ..]);
+        assert_eq!(PyArrayLike2::from_owned(array, py).into_vec(), [3, 4]);
+    });
+}
+
+#[test]
+fn extract_any_dispatches_on_dtype() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.array([[1.5, 2.5]])")
+            .extract::<AnyPyArrayLike<Ix2>>()
+            .unwrap();
+        let AnyPyArrayLike::F64(extracted_array) = extracted_array else {
+            panic!("expected float64, got {:?}", extracted_array);
+        };
+        assert_eq!(extracted_array.view(), array![[1.5, 2.5]]);
+
+        let extracted_array = eval(py, "np.array([1, 2, 3], dtype='int32')")
+            .extract::<AnyPyArrayLike<Ix1>>()
+            .unwrap();
+        let AnyPyArrayLike::I32(extracted_array) = extracted_array else {
+            panic!("expected int32, got {:?}", extracted_array);
+        };
+        assert_eq!(extracted_array.view(), array![1, 2, 3]);
+
+        let extracted_array = eval(
+            py,
+            "np.array([1, 2, 3], dtype=np.dtype('i4').newbyteorder('S'))",
+        )
+        .extract::<AnyPyArrayLike<Ix1>>()
+        .unwrap();
+        let AnyPyArrayLike::I32(extracted_array) = extracted_array else {
+            panic!("expected int32, got {:?}", extracted_array);
+        };
+        assert_eq!(extracted_array.view(), array![1, 2, 3]);
+
+        let extracted_array = eval(py, "[1, 2, 3]")
+            .extract::<AnyPyArrayLike<Ix1>>()
+            .unwrap();
+        let AnyPyArrayLike::I64(mut extracted_array) = extracted_array else {
+            panic!("expected int64, got {:?}", extracted_array);
+        };
+        assert!(!extracted_array.is_zero_copy());
+        assert_eq!(
+            extracted_array.provenance(),
+            ArrayLikeKind::BuiltFromSequence
+        );
+        assert!(extracted_array.try_view_mut().is_none());
+        assert_eq!(extracted_array.view(), array![1, 2, 3]);
+
+        let extracted_array = eval(py, "memoryview(np.array([1.0, 2.0]))")
+            .extract::<AnyPyArrayLike<Ix1>>()
+            .unwrap();
+        let AnyPyArrayLike::F64(extracted_array) = extracted_array else {
+            panic!("expected float64, got {:?}", extracted_array);
+        };
+        assert!(extracted_array.is_zero_copy());
+
+        assert!(eval(py, "np.array(['a', 'b'])")
+            .extract::<AnyPyArrayLike<Ix1>>()
+            .is_err());
+    });
+}
+
+#[test]
+fn extract_memoryview() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "memoryview(b'\\x01\\x02\\x03')")
+            .extract::<PyArrayLike1<u8>>()
+            .unwrap();
+        assert_eq!(extracted_array.source_writeable(), Some(false));
+        assert_eq!(extracted_array.view(), array![1, 2, 3]);
+
+        let extracted_array = eval(py, "memoryview(np.array([1.0, 2.0]))")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert!(extracted_array.source_writeable().is_some());
+        assert_eq!(extracted_array.view(), array![1.0, 2.0]);
+
+        let extracted_array = eval(py, "memoryview(np.array([1, 2], dtype='int32'))")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.source_writeable(), None);
+        assert_eq!(extracted_array.view(), array![1, 2]);
+    });
+}
+
+#[test]
+fn as_dyn_view_collects_mixed_dimensions() {
+    Python::with_gil(|py| {
+        let a = eval(py, "[1.0, 2.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let b = eval(py, "np.zeros((2, 3, 4))")
+            .extract::<PyArrayLike3<f64>>()
+            .unwrap();
+        let views = [a.as_dyn_view(), b.as_dyn_view()];
+        assert_eq!(views[0].shape(), [2]);
+        assert_eq!(views[1].shape(), [2, 3, 4]);
+        assert_eq!(views[0], array![1.0, 2.0].into_dyn());
+    });
+}
+
+#[test]
+fn try_readwrite_only_succeeds_for_writeable_arrays() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1.0, 2.0, 3.0])");
+        let extracted_array = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        let mut readwrite = extracted_array.try_readwrite().unwrap();
+        readwrite.as_array_mut()[1] = 20.0;
+        drop(readwrite);
+        assert_eq!(
+            py_array.extract::<PyArrayLike1<f64>>().unwrap().view(),
+            array![1.0, 20.0, 3.0]
+        );
+
+        let py_array = eval(py, "np.array([1.0, 2.0, 3.0])");
+        py_array
+            .getattr("flags")
+            .unwrap()
+            .setattr("writeable", false)
+            .unwrap();
+        let extracted_array = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        let extracted_array = extracted_array.try_readwrite().unwrap_err();
+        assert_eq!(extracted_array.view(), array![1.0, 2.0, 3.0]);
+
+        let extracted_array = eval(py, "[1.0, 2.0, 3.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let extracted_array = extracted_array.try_readwrite().unwrap_err();
+        assert_eq!(extracted_array.view(), array![1.0, 2.0, 3.0]);
+    });
+}
+
+#[test]
+fn t_reverses_axes() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.arange(6.0).reshape(2, 3)")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        let transposed = extracted_array.t();
+        assert_eq!(transposed.dim(), (3, 2));
+        assert_eq!(transposed, array![[0.0, 3.0], [1.0, 4.0], [2.0, 5.0]]);
+        assert_eq!(transposed.as_ptr(), extracted_array.view().as_ptr());
+    });
+}
+
+#[test]
+fn into_readwrite_copies_only_when_necessary() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([1.0, 2.0, 3.0])");
+        let extracted_array = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        let (mut readwrite, copied) = extracted_array.into_readwrite().unwrap();
+        assert!(!copied);
+        readwrite.as_array_mut()[0] = 10.0;
+        drop(readwrite);
+        assert_eq!(
+            py_array.extract::<PyArrayLike1<f64>>().unwrap().view(),
+            array![10.0, 2.0, 3.0]
+        );
+
+        let py_array = eval(py, "np.array([1.0, 2.0, 3.0])");
+        py_array
+            .getattr("flags")
+            .unwrap()
+            .setattr("writeable", false)
+            .unwrap();
+        let extracted_array = py_array.extract::<PyArrayLike1<f64>>().unwrap();
+        let (mut readwrite, copied) = extracted_array.into_readwrite().unwrap();
+        assert!(copied);
+        readwrite.as_array_mut()[0] = 10.0;
+        assert!(!readwrite.is(&py_array));
+        assert_eq!(
+            py_array.extract::<PyArrayLike1<f64>>().unwrap().view(),
+            array![1.0, 2.0, 3.0]
+        );
+
+        let extracted_array = eval(py, "[1.0, 2.0, 3.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let (readwrite, copied) = extracted_array.into_readwrite().unwrap();
+        assert!(copied);
+        assert_eq!(readwrite.as_array(), array![1.0, 2.0, 3.0]);
+    });
+}
+
+#[test]
+fn copies_made_during_extraction_are_not_writeable() {
+    Python::with_gil(|py| {
+        let unsafe_casting = ExtractOptions::new().unsafe_casting(true);
+        let default = ExtractOptions::new();
+        for (code, options) in [
+            ("np.array([1.5, 2.5, 3.5])", &unsafe_casting),
+            (
+                "np.array([1, 2, 3], dtype=np.dtype('i4').newbyteorder('S'))",
+                &default,
+            ),
+        ] {
+            let py_array = eval(py, code);
+            let extracted_array = PyArrayLike1::<i32>::extract_with(&py_array, options).unwrap();
+            assert_eq!(extracted_array.source_writeable(), Some(true));
+            let mut extracted_array = extracted_array.try_readwrite().unwrap_err();
+            assert!(!extracted_array.is_zero_copy());
+            assert!(extracted_array.try_view_mut().is_none());
+
+            let (mut readwrite, copied) = extracted_array.into_readwrite().unwrap();
+            assert!(copied, "{}", code);
+            readwrite.as_array_mut()[0] = 10;
+            assert!(!readwrite.is(&py_array));
+            drop(readwrite);
+            assert!(py_array.get_item(0).unwrap().extract::<f64>().unwrap() < 2.0);
+        }
+    });
+}
+
+#[test]
+fn gradient_of_linear_ramp() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.arange(5.0) * 3.0")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(
+            extracted_array.gradient(Axis(0), 0.5),
+            array![6.0, 6.0, 6.0, 6.0, 6.0]
+        );
+
+        let extracted_array = eval(py, "[[1, 2, 4], [1, 3, 9]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        assert_eq!(
+            extracted_array.gradient(Axis(1), 1.0),
+            array![[1.0, 1.5, 2.0], [2.0, 4.0, 6.0]]
+        );
+    });
+}
+
+#[test]
+fn quantize_into_uniform_bins() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[0.0, 0.5, 1.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.quantize(4, (0.0, 1.0)), array![0, 2, 3]);
+
+        let extracted_array = eval(py, "[-1.0, 0.3, 2.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.quantize(4, (0.0, 1.0)), array![0, 1, 3]);
+    });
+}
+
+#[test]
+fn extraction_error_names_received_type() {
+    Python::with_gil(|py| {
+        let err = eval(py, "{'a': 1.0}")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "Expected an array like of dimension 1 containing elements which can be safely casted to float64, got 'dict'."
+        ));
+
+        let err = eval(py, "'abc'")
+            .extract::<PyArrayLike1<Py<PyAny>>>()
+            .unwrap_err();
+        assert!(err.to_string().contains(", got 'str'."));
+    });
+}
+
+#[test]
+fn cow_arrays_avoid_copies() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.array([1.0, 2.0])")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let cow = extracted_array.as_cow();
+        assert!(cow.is_view());
+        assert_eq!(cow.as_ptr(), extracted_array.view().as_ptr());
+        let ptr = extracted_array.view().as_ptr();
+        extracted_array.into_cow(|cow| {
+            assert!(cow.is_view());
+            assert_eq!(cow.as_ptr(), ptr);
+            assert_eq!(cow, array![1.0, 2.0]);
+        });
+
+        let extracted_array = eval(py, "[1.0, 2.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert!(extracted_array.as_cow().is_view());
+        let ptr = extracted_array.view().as_ptr();
+        let sum = extracted_array.into_cow(|cow| {
+            assert!(cow.is_owned());
+            assert_eq!(cow.as_ptr(), ptr);
+            cow.sum()
+        });
+        assert_eq!(sum, 3.0);
+    });
+}
+
+#[test]
+fn strings_shall_not_be_split_into_characters() {
+    Python::with_gil(|py| {
+        let err = eval(py, "'abc'")
+            .extract::<PyArrayLike1<i32>>()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected an array like, got 'str'. Strings are not split into characters."));
+
+        let err = eval(py, "'abc'").extract::<PyArrayLike1<u8>>().unwrap_err();
+        assert!(err.to_string().contains("ExtractOptions::str_as_utf8"));
+
+        let extracted_array = eval(py, "b'abc'").extract::<PyArrayLike1<u8>>().unwrap();
+        assert_eq!(extracted_array.source_writeable(), Some(false));
+        assert_eq!(extracted_array.view(), array![b'a', b'b', b'c']);
+    });
+}
+
+#[test]
+fn as_standard_layout_copies_on_demand() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.arange(6.0).reshape(2, 3)")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        let standard = extracted_array.as_standard_layout();
+        assert!(standard.is_view());
+        assert_eq!(standard.as_ptr(), extracted_array.view().as_ptr());
+
+        for code in [
+            "np.arange(6.0).reshape(2, 3).T",
+            "np.arange(12.0).reshape(3, 4)[:, ::2]",
+            "np.arange(6.0).reshape(3, 2)[::-1]",
+        ] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike2<f64>>().unwrap();
+            let standard = extracted_array.as_standard_layout();
+            assert!(standard.is_owned());
+            assert!(standard.is_standard_layout());
+            assert_eq!(standard, extracted_array.view());
+        }
+    });
+}
+
+#[test]
+fn convert_between_dimension_types() {
+    Python::with_gil(|py| {
+        for code in [
+            "np.arange(6.0).reshape(2, 3)",
+            "[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]",
+        ] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike2<f64>>().unwrap();
+            let ptr = extracted_array.view().as_ptr();
+            let provenance = extracted_array.provenance();
+            let dyn_array = extracted_array.into_dyn();
+            assert_eq!(dyn_array.view().shape(), [2, 3]);
+            assert_eq!(dyn_array.view().as_ptr(), ptr);
+            assert_eq!(dyn_array.provenance(), provenance);
+
+            let err = dyn_array
+                .into_dimensionality::<Ix1>()
+                .map(|_| ())
+                .unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("Expected an array of dimension 1, got dimension 2."));
+        }
+
+        let py_array = eval(py, "np.zeros((2, 2))");
+        let mut extracted_array = py_array.extract::<PyArrayLike2<f64>>().unwrap();
+        extracted_array.try_view_mut().unwrap()[[0, 1]] = 1.0;
+        let mut dyn_array = extracted_array.into_dyn();
+        assert!(dyn_array.is_zero_copy());
+        dyn_array.try_view_mut().unwrap()[[1, 0]] = 2.0;
+        drop(dyn_array);
+        assert_eq!(
+            py_array.extract::<PyArrayLike2<f64>>().unwrap().view(),
+            array![[0.0, 1.0], [2.0, 0.0]]
+        );
+
+        let extracted_array = eval(py, "[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]")
+            .extract::<PyArrayLikeDyn<f64>>()
+            .unwrap();
+        let extracted_array = extracted_array.into_dimensionality::<Ix2>().unwrap();
+        assert_eq!(
+            extracted_array.view(),
+            array![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]
+        );
+    });
+}
+
+#[test]
+fn slice_access_in_fortran_order() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.asfortranarray(np.arange(6.0).reshape(2, 3))")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.as_slice(), None);
+        assert_eq!(
+            extracted_array.as_slice_f(),
+            Some(&[0.0, 3.0, 1.0, 4.0, 2.0, 5.0][..])
+        );
+        assert_eq!(
+            extracted_array.as_slice_memory_order(),
+            extracted_array.as_slice_f()
+        );
+        let owned = extracted_array.into_owned_array_with_order(MemoryOrder::F);
+        assert!(owned.t().is_standard_layout());
+
+        let extracted_array = eval(py, "np.asfortranarray(np.arange(24).reshape(2, 3, 4))")
+            .extract::<PyArrayLike3<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.as_slice(), None);
+        let slice = extracted_array.as_slice_f().unwrap();
+        assert_eq!(&slice[..4], [0, 12, 4, 16]);
+        assert_eq!(extracted_array.as_slice_memory_order(), Some(slice));
+        let owned = extracted_array.into_owned_array_with_order(MemoryOrder::C);
+        assert!(owned.is_standard_layout());
+        assert_eq!(owned.as_slice().unwrap()[..4], [0, 1, 2, 3]);
+
+        let extracted_array = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.as_slice_f(), None);
+        assert_eq!(
+            extracted_array.as_slice_memory_order(),
+            Some(&[1, 2, 3, 4][..])
+        );
+        let owned = extracted_array.into_owned_array_with_order(MemoryOrder::F);
+        assert_eq!(owned, array![[1, 2], [3, 4]]);
+        assert_eq!(owned.as_slice_memory_order(), Some(&[1, 3, 2, 4][..]));
+    });
+}
+
+#[test]
+fn order_agrees_with_slice_access() {
+    Python::with_gil(|py| {
+        for (code, order) in [
+            ("np.arange(6.0).reshape(2, 3)", Some(MemoryOrder::C)),
+            ("[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]", Some(MemoryOrder::C)),
+            (
+                "np.asfortranarray(np.arange(6.0).reshape(2, 3))",
+                Some(MemoryOrder::F),
+            ),
+            ("np.arange(6.0).reshape(3, 2).T", Some(MemoryOrder::F)),
+            ("np.arange(12.0).reshape(3, 4)[:, ::2]", None),
+        ] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike2<f64>>().unwrap();
+            assert_eq!(extracted_array.order(), order, "{}", code);
+            assert_eq!(
+                extracted_array.is_c_contiguous(),
+                extracted_array.as_slice().is_some()
+            );
+            assert_eq!(
+                extracted_array.is_f_contiguous(),
+                extracted_array.as_slice_f().is_some()
+            );
+        }
+
+        let array = ndarray::Array2::from_shape_vec((2, 3).f(), vec![0.0; 6]).unwrap();
+        let extracted_array = PyArrayLike2::from_owned(array, py);
+        assert_eq!(extracted_array.order(), Some(MemoryOrder::F));
+        assert!(extracted_array.as_slice_f().is_some());
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_iter_sums_elements() {
+    use ndarray::parallel::prelude::ParallelIterator;
+
+    Python::with_gil(|py| {
+        let extracted_array = PyArrayLike1::from_vec((1..=1000).collect::<Vec<i64>>(), py);
+        assert_eq!(extracted_array.par_iter().sum::<i64>(), 500500);
+    });
+}
+
+#[test]
+fn read_h5py_dataset_on_request() {
+    Python::with_gil(|py| {
+        // The test is skipped if h5py is not installed.
+        let Ok(h5py) = py.import_bound("h5py") else {
+            return;
+        };
+        let locals = [("h5py", h5py)].into_py_dict_bound(py);
+        py.run_bound(
+            r#"
+file = h5py.File("data.h5", "w", driver="core", backing_store=False)
+dataset = file.create_dataset("data", data=[[1.0, 2.0], [3.0, 4.0]])
+"#,
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+        let dataset = locals.get_item("dataset").unwrap();
+
+        let options = ExtractOptions::new().read_datasets(true);
+        let extracted_array = PyArrayLike2::<f64>::extract_with(&dataset, &options).unwrap();
+        assert_eq!(extracted_array.view(), array![[1.0, 2.0], [3.0, 4.0]]);
+    });
+}
+
+#[test]
+fn shape_has_one_length_per_axis() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.float64(1.0)")
+            .extract::<PyArrayLike0<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.shape(), [0; 0]);
+
+        let extracted_array = eval(py, "[1, 2, 3]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.shape(), [3]);
+        assert_eq!(extracted_array.len_of(Axis(0)), 3);
+
+        let extracted_array = eval(py, "np.zeros((2, 3, 4))")
+            .extract::<PyArrayLike3<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.shape(), [2, 3, 4]);
+        assert_eq!(extracted_array.len_of(Axis(2)), 4);
+
+        let extracted_array = eval(py, "np.zeros((2, 0, 4, 1))")
+            .extract::<PyArrayLikeDyn<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.shape(), [2, 0, 4, 1]);
+        assert_eq!(extracted_array.shape().len(), extracted_array.view().ndim());
+    });
+}
+
+#[test]
+fn extract_broadcast_to_runtime_shape() {
+    Python::with_gil(|py| {
+        let extracted_array =
+            PyArrayLike2::<f64>::extract_broadcast(&eval(py, "2.5"), (3, 4)).unwrap();
+        assert_eq!(
+            extracted_array.view(),
+            ndarray::Array2::from_elem((3, 4), 2.5)
+        );
+
+        let extracted_array =
+            PyArrayLike2::<i64>::extract_broadcast(&eval(py, "np.array([[1], [2], [3]])"), (3, 4))
+                .unwrap();
+        assert_eq!(
+            extracted_array.view(),
+            array![[1, 1, 1, 1], [2, 2, 2, 2], [3, 3, 3, 3]]
+        );
+
+        let err = PyArrayLike2::<i64>::extract_broadcast(&eval(py, "[1, 2]"), (3, 4)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot broadcast an array of shape [2] to shape [3, 4]."));
+    });
+}
+
+#[test]
+fn size_queries_match_numpy() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.float64(1.0)")
+            .extract::<PyArrayLike0<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.ndim(), 0);
+        assert_eq!(extracted_array.len(), 1);
+        assert!(!extracted_array.is_empty());
+        assert_eq!(extracted_array.nbytes(), 8);
+
+        let extracted_array = eval(py, "np.zeros((0, 3), dtype='int32')")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        assert_eq!(extracted_array.ndim(), 2);
+        assert_eq!(extracted_array.len(), 0);
+        assert!(extracted_array.is_empty());
+        assert_eq!(extracted_array.nbytes(), 0);
+
+        for extracted_array in [
+            eval(py, "np.zeros((2, 3, 4), dtype='int32')"),
+            eval(py, "[[[0] * 4] * 3] * 2"),
+        ]
+        .map(|ob| ob.extract::<PyArrayLikeDyn<i32>>().unwrap())
+        {
+            assert_eq!(extracted_array.ndim(), 3);
+            assert_eq!(extracted_array.len(), 24);
+            assert!(!extracted_array.is_empty());
+            assert_eq!(extracted_array.nbytes(), 96);
+        }
+    });
+}
+
+#[test]
+fn tile_repeats_array() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        assert_eq!(
+            extracted_array.tile(&[1, 2]).unwrap(),
+            array![[1, 2, 1, 2], [3, 4, 3, 4]]
+        );
+        assert_eq!(
+            extracted_array.tile(&[2, 1]).unwrap(),
+            array![[1, 2], [3, 4], [1, 2], [3, 4]]
+        );
+        assert_eq!(extracted_array.tile(&[0, 3]).unwrap().dim(), (0, 6));
+
+        let err = extracted_array.tile(&[2]).unwrap_err();
+        assert!(err.to_string().contains("Expected 2 repetition counts"));
+    });
+}
+
+#[test]
+fn select_with_boolean_mask() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[1, 2, 3, 4]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        let mask = eval(py, "np.array([True, False, True, False])")
+            .extract::<PyArrayLike1<bool>>()
+            .unwrap();
+        assert_eq!(extracted_array.select(&mask).unwrap().view(), array![1, 3]);
+
+        let mask = eval(py, "[True, False]")
+            .extract::<PyArrayLike1<bool>>()
+            .unwrap();
+        let err = extracted_array.select(&mask).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected a mask of length 4, got length 2."));
+    });
+}
+
+#[test]
+fn strides_count_elements() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.zeros((2, 3)).T")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.strides(), [1, 3]);
+
+        let extracted_array = eval(py, "np.arange(6)[::-2]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.strides(), [-2]);
+        assert_eq!(extracted_array.view(), array![5, 3, 1]);
+
+        let extracted_array = eval(py, "[[1, 2, 3], [4, 5, 6]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.strides(), [3, 1]);
+    });
+}
+
+#[test]
+fn detach_sends_data_to_thread() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.arange(10.0)")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let array = extracted_array.detach();
+        let sum = std::thread::spawn(move || array.sum()).join().unwrap();
+        assert_eq!(sum, 45.0);
+    });
+}
+
+#[test]
+fn extract_with_path_reports_conversion() {
+    Python::with_gil(|py| {
+        let options = ExtractOptions::new();
+        for (code, path) in [
+            ("np.array([1.0, 2.0])", CoercionPath::DirectNumpy),
+            ("memoryview(np.array([1.0, 2.0]))", CoercionPath::Buffer),
+            ("[1.0, 2.0]", CoercionPath::FlatSequence),
+            ("[[1.0, 2.0]]", CoercionPath::NestedSequence),
+            ("1.0", CoercionPath::ScalarBroadcast),
+            (
+                "np.array([1, 2], dtype='int32')",
+                CoercionPath::ArrayProtocol,
+            ),
+            (
+                "np.array([[1, 2]], dtype='float32')",
+                CoercionPath::ArrayProtocol,
+            ),
+            (
+                "np.array([1.0, 2.0], dtype='>f8')",
+                CoercionPath::ArrayProtocol,
+            ),
+        ] {
+            let (_, reported) =
+                PyArrayLikeDyn::<f64>::extract_with_path(&eval(py, code), &options).unwrap();
+            assert_eq!(reported, path, "{}", code);
+        }
+
+        let options = ExtractOptions::new().contiguous(MemoryOrder::C);
+        let (extracted_array, reported) = PyArrayLike2::<f64>::extract_with_path(
+            &eval(py, "np.asfortranarray(np.ones((2, 3)))"),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(reported, CoercionPath::DirectNumpy);
+        assert!(!extracted_array.is_zero_copy());
+        assert_eq!(
+            extracted_array.provenance(),
+            ArrayLikeKind::CopiedFromNdArray
+        );
+    });
+}
+
+#[test]
+fn broadcast_scalars_on_request() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.array(5.0)")
+            .extract::<PyArrayLike0<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array.view(), Array0::from_elem((), 5.0));
+        assert!(eval(py, "np.array(5.0)")
+            .extract::<PyArrayLike1<f64>>()
+            .is_err());
+
+        let options = ExtractOptions::new().broadcast_scalars(true);
+        for code in ["np.array(5.0)", "5.0", "np.float64(5.0)"] {
+            let (extracted_array, path) =
+                PyArrayLike1::<f64>::extract_with_path(&eval(py, code), &options).unwrap();
+            assert_eq!(extracted_array.view(), array![5.0]);
+            assert_eq!(path, CoercionPath::ScalarBroadcast);
+        }
+        let extracted_array =
+            PyArrayLike2::<i64>::extract_with(&eval(py, "np.array(3)"), &options).unwrap();
+        assert_eq!(extracted_array.view(), array![[3]]);
+        let extracted_array =
+            PyArrayLike1::<f64>::extract_with(&eval(py, "[1.0, 2.0]"), &options).unwrap();
+        assert_eq!(extracted_array.view(), array![1.0, 2.0]);
+    });
+}
+
+#[test]
+fn rolling_reduce_over_windows() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[1, 3, 2, 5]")
+            .extract::<PyArrayLike1<i32>>()
+            .unwrap();
+        let rolling_max = extracted_array
+            .rolling_reduce(2, |window| *window.iter().max().unwrap())
+            .unwrap();
+        assert_eq!(rolling_max, [3, 3, 5]);
+        let rolling_sum = extracted_array
+            .rolling_reduce(4, |window| window.sum())
+            .unwrap();
+        assert_eq!(rolling_sum, [11]);
+
+        for window in [0, 5] {
+            let err = extracted_array
+                .rolling_reduce(window, |window| window.sum())
+                .unwrap_err();
+            assert!(err.to_string().contains("Expected a window between 1 and"));
+        }
+    });
+}
+
+#[test]
+fn report_provenance() {
+    Python::with_gil(|py| {
+        for (code, kind) in [
+            ("np.array([1.0, 2.0])", ArrayLikeKind::BorrowedNdArray),
+            (
+                "memoryview(np.array([1.0, 2.0]))",
+                ArrayLikeKind::BorrowedNdArray,
+            ),
+            (
+                "np.array([1, 2], dtype='int32')",
+                ArrayLikeKind::CopiedFromNdArray,
+            ),
+            ("[1.0, 2.0]", ArrayLikeKind::BuiltFromSequence),
+            ("1.0", ArrayLikeKind::Scalar),
+        ] {
+            let extracted_array = eval(py, code).extract::<PyArrayLikeDyn<f64>>().unwrap();
+            assert_eq!(extracted_array.provenance(), kind, "{}", code);
+            assert_eq!(
+                extracted_array.is_zero_copy(),
+                kind == ArrayLikeKind::BorrowedNdArray
+            );
+        }
+
+        let options = ExtractOptions::new().contiguous(MemoryOrder::C);
+        let extracted_array =
+            PyArrayLike2::<f64>::extract_with(&eval(py, "np.ones((2, 3)).T"), &options).unwrap();
+        assert_eq!(
+            extracted_array.provenance(),
+            ArrayLikeKind::CopiedFromNdArray
+        );
+
+        let from_owned = PyArrayLike1::from_owned(array![1.0, 2.0], py);
+        assert_eq!(from_owned.provenance(), ArrayLikeKind::Owned);
+    });
+}
+
+#[test]
+fn py_token_allocates_results() {
+    fn doubled<'py>(array: &PyArrayLike1<'py, f64>) -> Bound<'py, PyArray1<f64>> {
+        array
+            .view()
+            .mapv(|x| 2.0 * x)
+            .into_pyarray_bound(array.py())
+    }
+
+    Python::with_gil(|py| {
+        for code in ["np.array([1.0, 2.0])", "[1.0, 2.0]"] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike1<f64>>().unwrap();
+            let result = doubled(&extracted_array);
+            assert_eq!(result.readonly().as_array(), array![2.0, 4.0]);
+        }
+    });
+}
+
+#[test]
+fn index_elements() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[1.0, 2.0, 3.0]")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        assert_eq!(extracted_array[2], 3.0);
+        assert_eq!(extracted_array.get(0), Some(&1.0));
+        assert_eq!(extracted_array.get(3), None);
+
+        let extracted_array = eval(py, "np.arange(6).reshape(2, 3)")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array[[1, 0]], 3);
+        assert_eq!(extracted_array.get((0, 2)), Some(&2));
+        assert_eq!(extracted_array.get([2, 0]), None);
+
+        let transposed = eval(py, "np.arange(6).reshape(2, 3).T")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert!(transposed.is_zero_copy());
+        assert_eq!(transposed[[2, 1]], 5);
+        assert_eq!(transposed.get([0, 1]), Some(&3));
+
+        let reversed = eval(py, "np.arange(4)[::-1]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        assert!(reversed.is_zero_copy());
+        assert_eq!(reversed[0], 3);
+        assert_eq!(reversed.get(3), Some(&0));
+    });
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds_panics() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[1, 2]").extract::<PyArrayLike1<i32>>().unwrap();
+        let _ = extracted_array[2];
+    });
+}
+
+#[test]
+fn would_borrow_predicts_copies() {
+    Python::with_gil(|py| {
+        let py_array = eval(py, "np.array([[1.0, 2.0], [3.0, 4.0]])");
+        assert!(PyArrayLike2::<f64>::would_borrow(&py_array));
+        assert!(py_array
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap()
+            .is_zero_copy());
+        assert!(PyArrayLike2::<f64>::would_borrow(&eval(
+            py,
+            "np.ones((2, 3)).T"
+        )));
+
+        assert!(!PyArrayLike2::<f32>::would_borrow(&py_array));
+        assert!(!PyArrayLike1::<f64>::would_borrow(&py_array));
+        assert!(!PyArrayLike2::<f64>::would_borrow(&eval(
+            py,
+            "[[1.0, 2.0]]"
+        )));
+        assert!(!PyArrayLike1::<f64>::would_borrow(&eval(
+            py,
+            "np.ma.array([1.0, 2.0], mask=[True, False])"
+        )));
+
+        // Buffers and DLPack tensors are borrowed, but only numpy arrays are predicted.
+        let buffer = eval(py, "memoryview(np.array([1.0, 2.0]))");
+        assert!(!PyArrayLike1::<f64>::would_borrow(&buffer));
+        assert!(buffer
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap()
+            .is_zero_copy());
+        let bytes = eval(py, "b'ab'");
+        assert!(!PyArrayLike1::<u8>::would_borrow(&bytes));
+        assert!(bytes.extract::<PyArrayLike1<u8>>().unwrap().is_zero_copy());
+        #[cfg(feature = "dlpack")]
+        {
+            let tensor = eval(
+                py,
+                "type('Tensor', (), {'data': np.arange(4, dtype='float64'), '__dlpack__': lambda self, **kwargs: self.data.__dlpack__(**kwargs), '__dlpack_device__': lambda self: self.data.__dlpack_device__()})()",
+            );
+            assert!(!PyArrayLike1::<f64>::would_borrow(&tensor));
+            assert!(tensor
+                .extract::<PyArrayLike1<f64>>()
+                .unwrap()
+                .is_zero_copy());
+        }
+        assert!(!PyArrayLike1::<f64>::would_borrow(&eval(py, "[1.0, 2.0]")));
+        assert!(!PyArrayLike0::<f64>::would_borrow(&eval(py, "1.0")));
+        assert!(!PyArrayLike1::<f64>::would_borrow(&eval(
+            py,
+            "np.array([1, 2], dtype='int32')"
+        )));
+    });
+}
+
+#[test]
+fn item_of_single_element() {
+    Python::with_gil(|py| {
+        for code in ["2.5", "np.array(2.5)", "np.float64(2.5)"] {
+            let extracted_array = eval(py, code).extract::<PyArrayLike0<f64>>().unwrap();
+            assert_eq!(extracted_array.item(), 2.5);
+            assert_eq!(extracted_array.item_ref(), &2.5);
+            assert_eq!(extracted_array.into_item(), 2.5);
+        }
+
+        let extracted_array = eval(py, "np.array([[7]])")
+            .extract::<PyArrayLikeDyn<i64>>()
+            .unwrap();
+        assert_eq!(extracted_array.try_item().unwrap(), 7);
+
+        let err = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLikeDyn<i64>>()
+            .unwrap()
+            .try_item()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected an array of size 1, got size 4."));
+    });
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn arraylike_attribute_extracts_views() {
+    use crate::arraylike;
+    use ndarray::{ArrayView1, ArrayView2};
+    use pyo3::{pyfunction, wrap_pyfunction_bound};
+
+    #[arraylike]
+    #[pyfunction]
+    fn weighted_sum_of_rows(ar: &ArrayView2<f64>, weights: &ArrayView1<f64>, scale: f64) -> f64 {
+        ar.dot(weights).sum() * scale
+    }
+
+    Python::with_gil(|py| {
+        let function = wrap_pyfunction_bound!(weighted_sum_of_rows, py).unwrap();
+        for code in [
+            "[[1.0, 2.0], [3.0, 4.0]]",
+            "np.array([[1.0, 2.0], [3.0, 4.0]])",
+        ] {
+            let result = function
+                .call1((eval(py, code), eval(py, "[1, 0]"), 2.0))
+                .unwrap();
+            assert_eq!(result.extract::<f64>().unwrap(), 8.0);
+        }
+        assert!(function
+            .call1((eval(py, "[1.0, 2.0]"), eval(py, "[1, 0]"), 2.0))
+            .is_err());
+    });
+}
+
+#[test]
+fn iterate_in_logical_order() {
+    Python::with_gil(|py| {
+        let transposed = eval(py, "np.arange(6, dtype='int64').reshape(2, 3).T")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert!(transposed.is_zero_copy());
+        assert!(transposed.iter().eq(transposed.view().iter()));
+        assert_eq!(
+            transposed.iter().copied().collect::<Vec<_>>(),
+            [0, 3, 1, 4, 2, 5]
+        );
+        let indexed: Vec<_> = transposed.indexed_iter().collect();
+        assert_eq!(indexed[1], ((0, 1), &3));
+        assert_eq!(indexed.len(), 6);
+
+        let owned = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        let mut sum = 0;
+        for x in &owned {
+            sum += x;
+        }
+        assert_eq!(sum, 10);
+    });
+}
+
+#[test]
+fn iterate_over_rows_and_axes() {
+    Python::with_gil(|py| {
+        let f_array = eval(
+            py,
+            "np.asfortranarray(np.arange(6, dtype='int64').reshape(2, 3))",
+        )
+        .extract::<PyArrayLike2<i64>>()
+        .unwrap();
+        assert!(f_array.is_zero_copy());
+        let rows: Vec<_> = f_array.rows().collect();
+        assert_eq!(rows, [array![0, 1, 2], array![3, 4, 5]]);
+        let columns: Vec<_> = f_array.axis_iter(Axis(1)).collect();
+        assert_eq!(columns, [array![0, 3], array![1, 4], array![2, 5]]);
+
+        let owned = eval(py, "[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        let view = owned.view();
+        for (index, row) in owned.rows().enumerate() {
+            assert_eq!(row, view.row(index));
+        }
+        assert_eq!(owned.rows().len(), 3);
+
+        let dyn_array = eval(py, "np.arange(8).reshape(2, 2, 2)")
+            .extract::<PyArrayLikeDyn<i64>>()
+            .unwrap();
+        let last = dyn_array.outer_iter().next_back().unwrap();
+        assert_eq!(last, array![[4, 5], [6, 7]].into_dyn());
+    });
+}
+
+#[test]
+fn py_token_creates_python_objects() {
+    Python::with_gil(|py| {
+        let owned = eval(py, "[1, 2, 3]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        let list = PyList::new_bound(owned.py(), owned.iter());
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.get_item(2).unwrap().extract::<i64>().unwrap(), 3);
+
+        let borrowed = eval(py, "np.zeros(2)")
+            .extract::<PyArrayLike1<f64>>()
+            .unwrap();
+        let err = PyZeroDivisionError::new_err("division by zero");
+        assert!(err.is_instance_of::<PyZeroDivisionError>(borrowed.py()));
+    });
+}
+
+#[test]
+fn copy_into_vec_in_logical_order() {
+    Python::with_gil(|py| {
+        let transposed = eval(py, "np.arange(6, dtype='int64').reshape(2, 3).T")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert!(transposed.as_slice().is_none());
+        assert_eq!(transposed.to_vec(), [0, 3, 1, 4, 2, 5]);
+        assert!(transposed.to_vec().iter().eq(transposed.view().iter()));
+        assert_eq!(transposed.to_vec_f(), [0, 1, 2, 3, 4, 5]);
+
+        let owned = eval(py, "[[1, 2], [3, 4]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        assert_eq!(owned.to_vec(), [1, 2, 3, 4]);
+        assert_eq!(owned.to_vec_f(), [1, 3, 2, 4]);
+
+        let scalar = eval(py, "7").extract::<PyArrayLike0<i64>>().unwrap();
+        assert_eq!(scalar.to_vec(), [7]);
+    });
+}
+
+#[test]
+fn sliding_windows() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[1, 2, 3, 4, 5]")
+            .extract::<PyArrayLike1<i32>>()
+            .unwrap();
+        let windows: Vec<_> = extracted_array.windows(3).collect();
+        assert_eq!(windows, [array![1, 2, 3], array![2, 3, 4], array![3, 4, 5]]);
+        assert_eq!(extracted_array.windows(5).len(), 1);
+        assert_eq!(extracted_array.windows(6).len(), 0);
+
+        let reversed = eval(py, "np.arange(4)[::-1]")
+            .extract::<PyArrayLike1<i64>>()
+            .unwrap();
+        assert_eq!(reversed.windows(2).next().unwrap(), array![3, 2]);
+    });
+}
+
+#[test]
+fn move_raw_vec_and_shape_out() {
+    Python::with_gil(|py| {
+        let owned = eval(py, "[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]")
+            .extract::<PyArrayLike2<f64>>()
+            .unwrap();
+        let ptr = owned.view().as_ptr();
+        let (vec, shape) = owned.try_into_raw_vec_and_shape().unwrap();
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(shape, [2, 3]);
+
+        let owned = PyArrayLike2::from_owned(array![[1, 2], [3, 4]], py);
+        let ptr = owned.view().as_ptr();
+        let (vec, shape) = owned.into_raw_vec_and_shape();
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!((vec, shape), (vec![1, 2, 3, 4], vec![2, 2]));
+
+        let borrowed = eval(py, "np.arange(6, dtype='int64').reshape(2, 3).T")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        let borrowed = borrowed.try_into_raw_vec_and_shape().unwrap_err();
+        assert!(borrowed.is_zero_copy());
+        let (vec, shape) = borrowed.into_raw_vec_and_shape();
+        assert_eq!(vec, [0, 3, 1, 4, 2, 5]);
+        assert_eq!(shape, [3, 2]);
+    });
+}
+
+#[test]
+fn extract_byte_swapped_array() {
+    Python::with_gil(|py| {
+        let swapped = eval(
+            py,
+            "np.array([[1.5, 2.5], [3.5, 4.5]]).astype(np.dtype('f8').newbyteorder('S'))",
+        );
+        let (extracted_array, path) =
+            PyArrayLike2::<f64>::extract_with_path(&swapped, &ExtractOptions::new()).unwrap();
+        assert_eq!(extracted_array.view(), array![[1.5, 2.5], [3.5, 4.5]]);
+        assert_eq!(
+            extracted_array.provenance(),
+            ArrayLikeKind::CopiedFromNdArray
+        );
+        assert_eq!(path, CoercionPath::ArrayProtocol);
+        assert!(!PyArrayLike2::<f64>::would_borrow(&swapped));
+
+        let swapped = eval(py, "np.arange(3, dtype=np.dtype('i4').newbyteorder('S'))");
+        let extracted_array = swapped.extract::<PyArrayLike1<f64>>().unwrap();
+        assert_eq!(extracted_array.view(), array![0.0, 1.0, 2.0]);
+    });
+}
+
+#[test]
+fn cast_element_type_after_extraction() {
+    Python::with_gil(|py| {
+        let borrowed = eval(py, "np.array([[1.5, -2.0], [0.25, 4.0]], dtype='float32')")
+            .extract::<PyArrayLike2<f32>>()
+            .unwrap();
+        assert!(borrowed.is_zero_copy());
+        let widened = borrowed.cast::<f64>();
+        assert_eq!(widened.provenance(), ArrayLikeKind::Owned);
+        assert_eq!(widened.view(), array![[1.5, -2.0], [0.25, 4.0]]);
+
+        let extracted_array = eval(py, "np.array([[1, 2], [3, 2**40]], dtype='int64')")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        let err = extracted_array.try_cast::<i32>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot cast the element at index (1, 1) into the target type."));
+
+        let narrowed = eval(py, "[[1, -2]]")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap()
+            .try_cast::<i8>()
+            .unwrap();
+        assert_eq!(narrowed.view(), array![[1_i8, -2]]);
+    });
+}
+
+#[test]
+fn iterate_over_chunks_along_axis() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "np.arange(20, dtype='int64').reshape(10, 2)")
+            .extract::<PyArrayLike2<i64>>()
+            .unwrap();
+        let chunks: Vec<_> = extracted_array.axis_chunks_iter(Axis(0), 3).collect();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0], array![[0, 1], [2, 3], [4, 5]]);
+        assert_eq!(chunks[3], array![[18, 19]]);
+        let rows: usize = chunks.iter().map(|chunk| chunk.nrows()).sum();
+        assert_eq!(rows, 10);
+
+        let columns: Vec<_> = extracted_array.axis_chunks_iter(Axis(1), 2).collect();
+        assert_eq!(columns, [extracted_array.view()]);
+    });
+}
+
+#[test]
+fn index_2d_extractions() {
+    Python::with_gil(|py| {
+        let extracted_array = eval(py, "[[1, 2, 3], [4, 5, 6]]")
+            .extract::<PyArrayLike2<i32>>()
+            .unwrap();
+        let total: i32 = (0..2)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .map(|(i, j)| extracted_array[[i, j]])
+            .sum();
+        assert_eq!(total, 21);
+        assert_eq!(extracted_array[(1, 2)], 6);
+
+        let dyn_array = eval(py, "np.arange(6).reshape(2, 3)")
+            .extract::<PyArrayLikeDyn<i64>>()
+            .unwrap();
+        assert_eq!(dyn_array[[1, 1]], 4);
+        assert_eq!(dyn_array[&[0, 2][..]], 2);
+        assert_eq!(dyn_array.get([2, 0]), None);
+
+        let c_array = eval(py, "np.asfortranarray(np.arange(4).reshape(2, 2))")
+            .extract::<PyArrayLikeC<i64, Ix2>>()
+            .unwrap();
+        assert_eq!(c_array[[1, 0]], 2);
+    });
+}
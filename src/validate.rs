@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+
+use ndarray::Array1;
+use num_traits::{Float, One, Zero};
+use numpy::{
+    pyo3::{exceptions::PyValueError, types::PyAnyMethods, Bound, FromPyObject, PyAny, PyResult},
+    Element,
+};
+
+use crate::{PyArrayLike1, PyArrayLike2};
+
+/// Extracts a time series given by `timestamps` and corresponding `values`.
+///
+/// Raises an error unless both arrays have the same length and the timestamps are strictly increasing.
+pub fn extract_time_series<'py, S, T>(
+    timestamps: &Bound<'py, PyAny>,
+    values: &Bound<'py, PyAny>,
+) -> PyResult<(PyArrayLike1<'py, S>, PyArrayLike1<'py, T>)>
+where
+    S: Element + FromPyObject<'py> + PartialOrd + 'static,
+    T: Element + FromPyObject<'py> + 'static,
+{
+    let timestamps = timestamps.extract::<PyArrayLike1<S>>()?;
+    let values = values.extract::<PyArrayLike1<T>>()?;
+
+    let (timestamps_view, values_view) = (timestamps.view(), values.view());
+    if timestamps_view.len() != values_view.len() {
+        return Err(PyValueError::new_err(format!(
+            "Expected timestamps and values of equal length, got {} and {}.",
+            timestamps_view.len(),
+            values_view.len()
+        )));
+    }
+    if let Some(index) = timestamps_view
+        .windows(2)
+        .into_iter()
+        .position(|pair| pair[0].partial_cmp(&pair[1]) != Some(Ordering::Less))
+    {
+        return Err(PyValueError::new_err(format!(
+            "Expected strictly increasing timestamps, but the timestamp at index {} does not exceed its predecessor.",
+            index + 1
+        )));
+    }
+    Ok((timestamps, values))
+}
+
+/// Extracts a vector of weights, which must be non-negative.
+///
+/// If `normalize` is set, the weights are divided by their sum, which raises an error if all weights are zero.
+pub fn extract_weights<'py, T>(
+    ob: &Bound<'py, PyAny>,
+    normalize: bool,
+) -> PyResult<PyArrayLike1<'py, T>>
+where
+    T: Element + Float + FromPyObject<'py> + 'static,
+{
+    let weights = ob.extract::<PyArrayLike1<T>>()?;
+
+    let view = weights.view();
+    if let Some(index) = view
+        .iter()
+        .position(|&weight| weight.is_nan() || weight < T::zero())
+    {
+        return Err(PyValueError::new_err(format!(
+            "Expected non-negative weights, but the weight at index {} is negative or NaN.",
+            index
+        )));
+    }
+    if !normalize {
+        return Ok(weights);
+    }
+    let sum = weights.sum();
+    if sum == T::zero() {
+        return Err(PyValueError::new_err(
+            "Expected weights with a positive sum, but all weights are zero.",
+        ));
+    }
+    let normalized = view.mapv(|weight| weight / sum);
+    Ok(PyArrayLike1::from_owned(normalized, ob.py()))
+}
+
+/// Extracts a matrix whose rows are probability distributions, i.e. non-negative and summing to one up to `tolerance`.
+pub fn extract_probability_simplex<'py, T>(
+    ob: &Bound<'py, PyAny>,
+    tolerance: T,
+) -> PyResult<PyArrayLike2<'py, T>>
+where
+    T: Element + Float + FromPyObject<'py> + 'static,
+{
+    let matrix = ob.extract::<PyArrayLike2<T>>()?;
+
+    for (index, row) in matrix.view().rows().into_iter().enumerate() {
+        if row.iter().any(|&p| p.is_nan() || p < T::zero()) {
+            return Err(PyValueError::new_err(format!(
+                "Expected non-negative probabilities, but row {} contains a negative or NaN entry.",
+                index
+            )));
+        }
+        let sum = row.sum();
+        if (sum - T::one()).abs() > tolerance {
+            return Err(PyValueError::new_err(format!(
+                "Expected every row to sum to 1, but row {} sums to {}.",
+                index,
+                sum.to_f64().unwrap_or(f64::NAN)
+            )));
+        }
+    }
+    Ok(matrix)
+}
+
+/// Extracts the adjacency matrix of a graph, which must be square and contain only zeros and ones.
+pub fn extract_adjacency_matrix<'py, T>(ob: &Bound<'py, PyAny>) -> PyResult<PyArrayLike2<'py, T>>
+where
+    T: Element + FromPyObject<'py> + Zero + One + PartialEq + 'static,
+{
+    let matrix = ob.extract::<PyArrayLike2<T>>()?;
+
+    let view = matrix.view();
+    if view.nrows() != view.ncols() {
+        return Err(PyValueError::new_err(format!(
+            "Expected a square adjacency matrix, got shape {:?}.",
+            view.dim()
+        )));
+    }
+    if let Some((index, _)) = view
+        .indexed_iter()
+        .find(|(_, entry)| !entry.is_zero() && !entry.is_one())
+    {
+        return Err(PyValueError::new_err(format!(
+            "Expected an adjacency matrix containing only 0 and 1, but the entry at index {:?} is neither.",
+            index
+        )));
+    }
+    Ok(matrix)
+}
+
+/// Extracts a one-hot matrix and decodes it into the column index of the single one in every row.
+pub fn extract_onehot<'py, T>(ob: &Bound<'py, PyAny>) -> PyResult<Array1<usize>>
+where
+    T: Element + FromPyObject<'py> + Zero + One + PartialEq + 'static,
+{
+    let matrix = ob.extract::<PyArrayLike2<T>>()?;
+
+    let view = matrix.view();
+    let mut indices = Array1::zeros(view.nrows());
+    for (row_index, row) in view.rows().into_iter().enumerate() {
+        let mut hot = row.iter().enumerate().filter(|(_, entry)| !entry.is_zero());
+        match (hot.next(), hot.next()) {
+            (Some((index, entry)), None) if entry.is_one() => indices[row_index] = index,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Expected a one-hot matrix with a single 1 and otherwise 0 in every row, but row {} is not one-hot.",
+                    row_index
+                )))
+            }
+        }
+    }
+    Ok(indices)
+}